@@ -0,0 +1,213 @@
+//! Persisted per-file read cursors
+//!
+//! Resume positions used to live in the frontend and were lost or misapplied after a
+//! crash. Cursors are stored in their own store file, keyed by a hash of the absolute
+//! log path, together with a file identity so a rotated log invalidates its cursor.
+//! log_fingerprint uses the same hash to tell whether two paths hold the same log.
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use tauri_plugin_store::StoreExt;
+
+use crate::error::LogError;
+use crate::{open_log_file, open_log_reader, read_raw_line};
+
+/// Store file the cursors are persisted in
+const CURSOR_STORE: &str = "read-cursors.json";
+
+/// Resume position in a log, plus the identity of the file it was taken from
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ReadCursor {
+    pub line: usize,
+    pub byte_offset: u64,
+    /// File size when the cursor was saved
+    pub file_size: u64,
+    /// Hash of the first line, which changes when SC starts a new Game.log
+    pub first_line_hash: String,
+}
+
+/// Size and first-line hash of a log on disk
+#[derive(Debug, PartialEq)]
+struct FileIdentity {
+    size: u64,
+    first_line_hash: String,
+}
+
+/// 64-bit FNV-1a, hex encoded. Used instead of DefaultHasher because store keys
+/// must stay stable across Rust releases.
+fn fnv1a_hex(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Bytes hashed from each end of a file by log_fingerprint
+const FINGERPRINT_EDGE_BYTES: u64 = 4096;
+
+/// Cheap content fingerprint of a log: hash of its size plus the first and last 4KB,
+/// so a copy or symlink of a log matches it without reading hundreds of MB
+#[tauri::command]
+pub fn log_fingerprint(path: &str) -> Result<String, LogError> {
+    let mut file = open_log_file(path)?;
+    let size = file.metadata()?.len();
+
+    let mut bytes = size.to_le_bytes().to_vec();
+    file.by_ref().take(FINGERPRINT_EDGE_BYTES).read_to_end(&mut bytes)?;
+    if size > FINGERPRINT_EDGE_BYTES {
+        // Small files are already covered in full by the head
+        file.seek(SeekFrom::Start(size.saturating_sub(FINGERPRINT_EDGE_BYTES).max(FINGERPRINT_EDGE_BYTES)))?;
+        file.read_to_end(&mut bytes)?;
+    }
+
+    Ok(fnv1a_hex(&bytes))
+}
+
+/// Store key for a log: hash of its absolute path
+fn cursor_key(path: &str) -> String {
+    let absolute = std::fs::canonicalize(path).unwrap_or_else(|_| Path::new(path).to_path_buf());
+    fnv1a_hex(absolute.to_string_lossy().as_bytes())
+}
+
+fn file_identity(path: &str) -> Result<FileIdentity, LogError> {
+    let size = std::fs::metadata(path)?.len();
+    let mut reader = open_log_reader(path)?;
+    let mut first_line = Vec::new();
+    read_raw_line(&mut reader, &mut first_line)?;
+
+    Ok(FileIdentity {
+        size,
+        first_line_hash: fnv1a_hex(&first_line),
+    })
+}
+
+/// A cursor still applies if the file kept its first line and did not shrink
+fn cursor_matches(cursor: &ReadCursor, identity: &FileIdentity) -> bool {
+    cursor.first_line_hash == identity.first_line_hash && identity.size >= cursor.file_size
+}
+
+/// Persist the resume position for a log
+#[tauri::command]
+pub fn save_read_cursor(
+    path: String,
+    line: usize,
+    byte_offset: u64,
+    app: tauri::AppHandle,
+) -> Result<(), LogError> {
+    let identity = file_identity(&path)?;
+    let cursor = ReadCursor {
+        line,
+        byte_offset,
+        file_size: identity.size,
+        first_line_hash: identity.first_line_hash,
+    };
+
+    let store = app.store(CURSOR_STORE).map_err(|e| LogError::Io(e.to_string()))?;
+    store.set(cursor_key(&path), serde_json::json!(cursor));
+    store.save().map_err(|e| LogError::Io(e.to_string()))
+}
+
+/// Saved resume position for a log, or None if there is none or the file was
+/// rotated since (a stale cursor is removed)
+#[tauri::command]
+pub fn load_read_cursor(path: String, app: tauri::AppHandle) -> Result<Option<ReadCursor>, LogError> {
+    let store = app.store(CURSOR_STORE).map_err(|e| LogError::Io(e.to_string()))?;
+    let key = cursor_key(&path);
+    let Some(cursor) = store
+        .get(&key)
+        .and_then(|value| serde_json::from_value::<ReadCursor>(value).ok())
+    else {
+        return Ok(None);
+    };
+
+    let identity = match file_identity(&path) {
+        Ok(identity) => identity,
+        Err(LogError::NotFound) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    if cursor_matches(&cursor, &identity) {
+        Ok(Some(cursor))
+    } else {
+        store.delete(&key);
+        store.save().map_err(|e| LogError::Io(e.to_string()))?;
+        Ok(None)
+    }
+}
+
+/// Forget every saved cursor, e.g. when the user logs out
+pub fn clear_read_cursors(app: &tauri::AppHandle) -> Result<(), LogError> {
+    let store = app.store(CURSOR_STORE).map_err(|e| LogError::Io(e.to_string()))?;
+    store.clear();
+    store.save().map_err(|e| LogError::Io(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_invalidated_by_rotation() {
+        let path = crate::tests::write_temp_log("cursor", "<2024-01-01T12:00:00.000Z> Log started\nsecond\n");
+        let path_str = path.to_str().unwrap();
+        let identity = file_identity(path_str).unwrap();
+        let cursor = ReadCursor {
+            line: 2,
+            byte_offset: identity.size,
+            file_size: identity.size,
+            first_line_hash: identity.first_line_hash.clone(),
+        };
+        assert!(cursor_matches(&cursor, &identity));
+
+        // Appending keeps the cursor valid
+        std::fs::write(&path, "<2024-01-01T12:00:00.000Z> Log started\nsecond\nthird\n").unwrap();
+        assert!(cursor_matches(&cursor, &file_identity(path_str).unwrap()));
+
+        // A new session rewrites the first line
+        std::fs::write(&path, "<2024-01-02T09:00:00.000Z> Log started\nsecond\nthird\n").unwrap();
+        assert!(!cursor_matches(&cursor, &file_identity(path_str).unwrap()));
+
+        // Truncation with the same first line
+        std::fs::write(&path, "<2024-01-01T12:00:00.000Z> Log started\n").unwrap();
+        assert!(!cursor_matches(&cursor, &file_identity(path_str).unwrap()));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_log_fingerprint_matches_copies() {
+        let content = format!("<2024-01-01T12:00:00.000Z> Log started\n{}end\n", "x".repeat(20_000));
+        let original = crate::tests::write_temp_log("fingerprint", &content);
+        let copy = crate::tests::write_temp_log("fingerprint_copy", &content);
+        let other = crate::tests::write_temp_log("fingerprint_other", &content.replace("end", "END"));
+
+        let fingerprint = log_fingerprint(original.to_str().unwrap()).unwrap();
+        assert_eq!(log_fingerprint(copy.to_str().unwrap()).unwrap(), fingerprint);
+        assert_ne!(log_fingerprint(other.to_str().unwrap()).unwrap(), fingerprint);
+
+        for path in [original, copy, other] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_cursor_offsets_past_4gb_round_trip() {
+        let cursor = ReadCursor {
+            line: 40_000_000,
+            byte_offset: 5_000_000_123,
+            file_size: u64::MAX,
+            first_line_hash: fnv1a_hex(b"<2024-01-01T12:00:00.000Z> Log started"),
+        };
+        let json = serde_json::to_value(&cursor).unwrap();
+        assert_eq!(json["byte_offset"], serde_json::json!(5_000_000_123u64));
+        assert_eq!(serde_json::from_value::<ReadCursor>(json).unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_cursor_key_stable() {
+        assert_eq!(fnv1a_hex(b""), "cbf29ce484222325");
+        assert_eq!(fnv1a_hex(b"a"), "af63dc4c8601ec8c");
+        assert_eq!(cursor_key("/no/such/Game.log"), fnv1a_hex(b"/no/such/Game.log"));
+    }
+}
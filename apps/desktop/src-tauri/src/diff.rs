@@ -0,0 +1,151 @@
+//! Comparing the events of two logs
+//!
+//! For a friend's session against one's own, or a log from before and after a patch.
+//! Events are compared by a normalized signature (no timestamp, digits collapsed) so
+//! ids and coordinates don't make every line unique. Only 64-bit hashes of the
+//! signatures are kept while comparing, plus one example line per unique event.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use crate::error::LogError;
+use crate::events::classify_line;
+use crate::{contains_event_marker, for_each_line, TIMESTAMP_RE};
+
+/// An event found in only one of the two logs
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct DiffEntry {
+    pub event_type: String,
+    /// First line with this signature
+    pub example_line: String,
+    /// Line number of `example_line`
+    pub line_number: usize,
+    /// Lines with this signature
+    pub count: usize,
+}
+
+/// Events of log A missing from log B and the other way round, each in file order
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct LogDiff {
+    pub only_in_a: Vec<DiffEntry>,
+    pub only_in_b: Vec<DiffEntry>,
+}
+
+/// Hash of a line with its timestamp dropped and every run of digits replaced by `#`
+fn signature_hash(line: &str) -> u64 {
+    let content = TIMESTAMP_RE.find(line).map_or(line, |m| &line[m.end()..]);
+    let mut hasher = DefaultHasher::new();
+    let mut in_digits = false;
+    for byte in content.bytes() {
+        let is_digit = byte.is_ascii_digit();
+        if !(is_digit && in_digits) {
+            (if is_digit { b'#' } else { byte }).hash(&mut hasher);
+        }
+        in_digits = is_digit;
+    }
+    hasher.finish()
+}
+
+/// Call `visit` with the line number and text of every marker-matched line
+fn for_each_event(path: &str, mut visit: impl FnMut(usize, &str)) -> Result<(), LogError> {
+    for_each_line(path, 0, |line_number, line| {
+        if contains_event_marker(line) {
+            visit(line_number, line);
+        }
+    })
+}
+
+fn signature_hashes(path: &str) -> Result<HashSet<u64>, LogError> {
+    let mut hashes = HashSet::new();
+    for_each_event(path, |_, line| {
+        hashes.insert(signature_hash(line));
+    })?;
+    Ok(hashes)
+}
+
+/// Events of `path` whose signature is not in `other`, one entry per signature
+fn unique_events(path: &str, other: &HashSet<u64>) -> Result<Vec<DiffEntry>, LogError> {
+    let mut entries: Vec<DiffEntry> = Vec::new();
+    let mut index_by_hash: HashMap<u64, usize> = HashMap::new();
+    for_each_event(path, |line_number, line| {
+        let hash = signature_hash(line);
+        if other.contains(&hash) {
+            return;
+        }
+        match index_by_hash.get(&hash) {
+            Some(&index) => entries[index].count += 1,
+            None => {
+                index_by_hash.insert(hash, entries.len());
+                entries.push(DiffEntry {
+                    event_type: classify_line(line),
+                    example_line: line.to_string(),
+                    line_number,
+                    count: 1,
+                });
+            }
+        }
+    })?;
+    Ok(entries)
+}
+
+/// Events that appear in only one of logs `a` and `b`, ignoring timestamps and numbers
+#[tauri::command]
+pub fn diff_logs(a: &str, b: &str) -> Result<LogDiff, LogError> {
+    let hashes_a = signature_hashes(a)?;
+    let hashes_b = signature_hashes(b)?;
+    Ok(LogDiff {
+        only_in_a: unique_events(a, &hashes_b)?,
+        only_in_b: unique_events(b, &hashes_a)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_ignores_timestamps_and_numbers() {
+        assert_eq!(
+            signature_hash("<2024-01-01T12:00:00.000Z> <Actor Death> 'Npc' [123] killed"),
+            signature_hash("<2024-02-03T08:30:00.000Z> <Actor Death> 'Npc' [98765] killed")
+        );
+        assert_ne!(
+            signature_hash("<2024-01-01T12:00:00.000Z> <Actor Death> 'Npc' killed"),
+            signature_hash("<2024-01-01T12:00:00.000Z> <Actor Death> 'Player' killed")
+        );
+    }
+
+    #[test]
+    fn test_diff_logs() {
+        let a = crate::tests::write_temp_log(
+            "diff_a",
+            "<2024-01-01T12:00:00.000Z> <SystemQuit> bye [1]\n\
+             <2024-01-01T12:00:01.000Z> <Actor Death> old format [2]\n\
+             <2024-01-01T12:00:02.000Z> <Actor Death> old format [3]\n\
+             <2024-01-01T12:00:03.000Z> plain line only in a\n",
+        );
+        let b = crate::tests::write_temp_log(
+            "diff_b",
+            "<2024-06-01T09:00:00.000Z> <SystemQuit> bye [7]\n\
+             <2024-06-01T09:00:01.000Z> <Actor Death> new format\n",
+        );
+
+        let diff = diff_logs(a.to_str().unwrap(), b.to_str().unwrap()).unwrap();
+        assert_eq!(
+            diff.only_in_a,
+            vec![DiffEntry {
+                event_type: "actor_death".to_string(),
+                example_line: "<2024-01-01T12:00:01.000Z> <Actor Death> old format [2]".to_string(),
+                line_number: 1,
+                count: 2,
+            }]
+        );
+        assert_eq!(diff.only_in_b.len(), 1);
+        assert_eq!(diff.only_in_b[0].line_number, 1);
+
+        for path in [a, b] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
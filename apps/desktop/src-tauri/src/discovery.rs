@@ -0,0 +1,364 @@
+//! Locating Game.log files on disk
+
+use std::path::{Path, PathBuf};
+
+use tauri_plugin_fs::FsExt;
+
+use crate::error::LogError;
+
+/// File name patterns find_latest_log matches when none are given
+const DEFAULT_LOG_PATTERNS: &[&str] = &["Game.log", "Game*.log"];
+
+/// Match a file name against a pattern where `*` matches any run of characters
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == name;
+    };
+    let Some(mut remaining) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut pieces: Vec<&str> = rest.split('*').collect();
+    let last = pieces.pop().unwrap_or_default();
+    for piece in pieces {
+        match remaining.find(piece) {
+            Some(index) => remaining = &remaining[index + piece.len()..],
+            None => return false,
+        }
+    }
+    remaining.len() >= last.len() && remaining.ends_with(last)
+}
+
+/// Most recently modified file in `dir` whose name matches one of `patterns`
+/// (default `Game.log` and `Game*.log`). None if the directory doesn't exist or
+/// nothing matches.
+#[tauri::command]
+pub fn find_latest_log(dir: String, patterns: Option<Vec<String>>) -> Option<String> {
+    let patterns: Vec<String> = match patterns {
+        Some(patterns) if !patterns.is_empty() => patterns,
+        _ => DEFAULT_LOG_PATTERNS.iter().map(|p| p.to_string()).collect(),
+    };
+
+    std::fs::read_dir(Path::new(&dir))
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            patterns.iter().any(|pattern| glob_match(pattern, &name))
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some((crate::modified_ms(&metadata).unwrap_or(0), entry.path()))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path.to_string_lossy().into_owned())
+}
+
+/// Release channels, each with its own folder under the StarCitizen library root
+const SC_CHANNELS: &[&str] = &["LIVE", "PTU", "EPTU"];
+
+/// A Game.log location for one release channel
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct LogLocation {
+    pub channel: String,
+    pub path: String,
+    pub exists: bool,
+}
+
+/// StarCitizen library roots from `reg query` output: the RSI Launcher's install
+/// location, with the game installed next to it by default
+#[cfg(any(windows, test))]
+fn library_roots_from_registry(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("InstallLocation"))
+        .filter_map(|rest| rest.split_once("REG_SZ").map(|(_, value)| value.trim()))
+        .filter(|location| location.to_ascii_lowercase().contains("rsi launcher"))
+        .filter_map(|location| {
+            let (parent, _) = location.trim_end_matches('\\').rsplit_once('\\')?;
+            Some(format!("{}\\StarCitizen", parent))
+        })
+        .collect()
+}
+
+/// Library roots recorded by the RSI Launcher installer in the uninstall registry keys
+#[cfg(windows)]
+fn registry_library_roots() -> Vec<PathBuf> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let keys = [
+        r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+        r"HKLM\SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+        r"HKCU\SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    ];
+    keys.into_iter()
+        .filter_map(|key| {
+            std::process::Command::new("reg")
+                .args(["query", key, "/s", "/v", "InstallLocation"])
+                .creation_flags(CREATE_NO_WINDOW)
+                .output()
+                .ok()
+        })
+        .flat_map(|output| library_roots_from_registry(&String::from_utf8_lossy(&output.stdout)))
+        .map(PathBuf::from)
+        .collect()
+}
+
+#[cfg(not(windows))]
+fn registry_library_roots() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+/// Candidate StarCitizen library roots. The first is the launcher's default and is
+/// always returned; the others only if they exist.
+fn library_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+
+    if cfg!(windows) {
+        let program_files = std::env::var("ProgramFiles").unwrap_or_else(|_| r"C:\Program Files".to_string());
+        roots.push(Path::new(&program_files).join(r"Roberts Space Industries\StarCitizen"));
+        roots.extend(registry_library_roots());
+        // Libraries moved to another drive from the launcher settings
+        for drive in 'C'..='Z' {
+            roots.push(PathBuf::from(format!(r"{}:\Roberts Space Industries\StarCitizen", drive)));
+            roots.push(PathBuf::from(format!(r"{}:\Program Files\Roberts Space Industries\StarCitizen", drive)));
+        }
+    } else if let Some(home) = std::env::var_os("HOME") {
+        // Default Lutris/Wine prefix on Linux
+        roots.push(Path::new(&home).join("Games/star-citizen/drive_c/Program Files/Roberts Space Industries/StarCitizen"));
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    roots
+        .into_iter()
+        .enumerate()
+        .filter(|(i, root)| *i == 0 || root.is_dir())
+        .map(|(_, root)| root)
+        .filter(|root| seen.insert(root.to_string_lossy().to_lowercase()))
+        .collect()
+}
+
+/// LIVE/PTU/EPTU Game.log paths under each library root
+fn locations_for_roots(roots: &[PathBuf]) -> Vec<LogLocation> {
+    roots
+        .iter()
+        .flat_map(|root| {
+            SC_CHANNELS.iter().map(move |channel| {
+                let path = root.join(channel).join("Game.log");
+                LogLocation {
+                    channel: channel.to_string(),
+                    exists: path.is_file(),
+                    path: path.to_string_lossy().into_owned(),
+                }
+            })
+        })
+        .collect()
+}
+
+/// Likely Game.log locations for each Star Citizen release channel, from the default
+/// install folder, the RSI Launcher's registry entries and other drives
+#[tauri::command]
+pub fn detect_sc_log_paths() -> Vec<LogLocation> {
+    locations_for_roots(&library_roots())
+}
+
+/// Entries of a channel folder (LIVE, PTU, ...), any of which identifies one
+const CHANNEL_DIR_ENTRIES: &[&str] = &["Game.log", "logbackups", "Bin64", "Data.p4k"];
+
+fn is_channel_dir(dir: &Path) -> bool {
+    CHANNEL_DIR_ENTRIES.iter().any(|entry| dir.join(entry).exists())
+}
+
+/// Whether `dir` is part of a Star Citizen install: a channel folder, a library root
+/// holding one, or a channel's logbackups folder
+fn looks_like_sc_dir(dir: &Path) -> bool {
+    is_channel_dir(dir)
+        || SC_CHANNELS.iter().any(|channel| is_channel_dir(&dir.join(channel)))
+        || dir
+            .file_name()
+            .is_some_and(|name| name.eq_ignore_ascii_case("logbackups"))
+}
+
+/// Add a Star Citizen folder (and everything below it) to the fs scope. The
+/// persisted-scope plugin saves scope changes, so access survives restarts and the
+/// folder doesn't have to be picked again every launch.
+#[tauri::command]
+pub fn grant_log_directory(dir: String, app: tauri::AppHandle) -> Result<(), String> {
+    let path = Path::new(&dir);
+    if !path.is_dir() {
+        return Err(format!("Not a folder: {}", dir));
+    }
+    if !looks_like_sc_dir(path) {
+        return Err(format!("Not a Star Citizen folder: {}", dir));
+    }
+
+    app.fs_scope().allow_directory(path, true).map_err(|e| e.to_string())
+}
+
+/// File name patterns of the logs list_log_files reports and delete_log_file removes
+const LOG_FILE_PATTERNS: &[&str] = &["*.log", "*.log.gz"];
+
+fn is_log_file_name(name: &str) -> bool {
+    LOG_FILE_PATTERNS.iter().any(|pattern| glob_match(pattern, name))
+}
+
+/// A log on disk, for the log manager
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct LogFileInfo {
+    pub path: String,
+    pub size_bytes: u64,
+    pub modified_ms: Option<i64>,
+}
+
+fn push_log_files(dir: &Path, files: &mut Vec<LogFileInfo>) -> Result<(), LogError> {
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        if !is_log_file_name(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        let Some(metadata) = entry.metadata().ok().filter(|m| m.is_file()) else {
+            continue;
+        };
+        files.push(LogFileInfo {
+            path: entry.path().to_string_lossy().into_owned(),
+            size_bytes: metadata.len(),
+            modified_ms: crate::modified_ms(&metadata),
+        });
+    }
+    Ok(())
+}
+
+/// Logs in `dir` and its `logbackups` folder (where SC moves old Game.logs), newest
+/// first, with their sizes so the user can see what is taking up space
+#[tauri::command]
+pub fn list_log_files(dir: String) -> Result<Vec<LogFileInfo>, LogError> {
+    let dir = Path::new(&dir);
+    let mut files = Vec::new();
+    push_log_files(dir, &mut files)?;
+    let backups = dir.join("logbackups");
+    if backups.is_dir() {
+        push_log_files(&backups, &mut files)?;
+    }
+
+    files.sort_by(|a, b| b.modified_ms.cmp(&a.modified_ms).then_with(|| a.path.cmp(&b.path)));
+    Ok(files)
+}
+
+/// Delete a log from the log manager. Only `.log` and `.log.gz` files inside a
+/// folder granted with grant_log_directory are removed, and never a watched log.
+#[tauri::command]
+pub fn delete_log_file(path: String, app: tauri::AppHandle) -> Result<(), LogError> {
+    let path = Path::new(&path);
+    let metadata = std::fs::symlink_metadata(path)?;
+    if !metadata.is_file() {
+        return Err(LogError::NotAFile);
+    }
+    let is_log = path
+        .file_name()
+        .is_some_and(|name| is_log_file_name(&name.to_string_lossy()));
+    if !is_log || !app.fs_scope().is_allowed(path) {
+        return Err(LogError::OutsideScope);
+    }
+    if crate::watcher::is_watched(path) {
+        return Err(LogError::InUse);
+    }
+
+    Ok(std::fs::remove_file(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("Game.log", "Game.log"));
+        assert!(glob_match("Game*.log", "Game Build(9000) 01 Jan 24 (12 00 00).log"));
+        assert!(glob_match("Game*.log", "Game.log"));
+        assert!(!glob_match("Game*.log", "Game.log.gz"));
+        assert!(!glob_match("Game*.log", "Launcher.log"));
+        assert!(glob_match("*.log*", "Game.log.gz"));
+    }
+
+    #[test]
+    fn test_find_latest_log_picks_newest_match() {
+        let dir = std::env::temp_dir().join(format!("picologs_test_discovery_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let old = dir.join("Game-old.log");
+        let new = dir.join("Game.log");
+        std::fs::write(&old, "old").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        std::fs::write(&new, "new").unwrap();
+
+        let earlier = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        std::fs::File::options().write(true).open(&old).unwrap().set_modified(earlier).unwrap();
+
+        let found = find_latest_log(dir.to_string_lossy().into_owned(), None);
+        assert_eq!(found, Some(new.to_string_lossy().into_owned()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(find_latest_log(dir.to_string_lossy().into_owned(), None), None);
+    }
+
+    #[test]
+    fn test_library_roots_from_registry() {
+        let output = "\r\nHKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\81bfc699-f883-50c7-b674-2483b6baae23\r\n    \
+                      InstallLocation    REG_SZ    D:\\Games\\Roberts Space Industries\\RSI Launcher\\\r\n\r\n\
+                      HKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\Other\r\n    \
+                      InstallLocation    REG_SZ    C:\\Program Files\\Other App\r\n";
+        assert_eq!(
+            library_roots_from_registry(output),
+            vec!["D:\\Games\\Roberts Space Industries\\StarCitizen".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_locations_for_roots_reports_existence() {
+        let root = std::env::temp_dir().join(format!("picologs_test_sc_root_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("LIVE")).unwrap();
+        std::fs::write(root.join("LIVE").join("Game.log"), "").unwrap();
+
+        let locations = locations_for_roots(std::slice::from_ref(&root));
+        let channels: Vec<_> = locations.iter().map(|l| (l.channel.as_str(), l.exists)).collect();
+        assert_eq!(channels, vec![("LIVE", true), ("PTU", false), ("EPTU", false)]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_looks_like_sc_dir() {
+        let root = std::env::temp_dir().join(format!("picologs_test_sc_grant_{}", std::process::id()));
+        let live = root.join("LIVE");
+        std::fs::create_dir_all(live.join("logbackups")).unwrap();
+
+        assert!(looks_like_sc_dir(&live));
+        assert!(looks_like_sc_dir(&root));
+        assert!(looks_like_sc_dir(&live.join("logbackups")));
+        assert!(!looks_like_sc_dir(&std::env::temp_dir()));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_list_log_files_includes_backups() {
+        let dir = std::env::temp_dir().join(format!("picologs_test_list_logs_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("logbackups")).unwrap();
+        std::fs::write(dir.join("Game.log"), "current").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        let old = dir.join("logbackups").join("Game Build(1) 01 Jan 24.log.gz");
+        std::fs::write(&old, "old").unwrap();
+        let earlier = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        std::fs::File::options().write(true).open(&old).unwrap().set_modified(earlier).unwrap();
+
+        let files = list_log_files(dir.to_string_lossy().into_owned()).unwrap();
+        let names: Vec<String> = files
+            .iter()
+            .map(|file| Path::new(&file.path).file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["Game.log", "Game Build(1) 01 Jan 24.log.gz"]);
+        assert_eq!(files[0].size_bytes, 7);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,98 @@
+//! Typed errors returned by the log-reading commands
+
+use std::fmt;
+use std::io;
+
+/// Error returned to the frontend by log-reading commands.
+///
+/// Serialized as `{ "kind": "not_found" }` or `{ "kind": "io", "details": "..." }`, so
+/// the UI can branch on `kind` (and decide whether to retry) instead of matching
+/// message strings. Invalid UTF-8 is not an error: lines are decoded lossily.
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", content = "details", rename_all = "snake_case")]
+pub enum LogError {
+    NotFound,
+    PermissionDenied,
+    /// The path is a directory (or another non-file), e.g. a mis-selected folder
+    NotAFile,
+    Io(String),
+    /// A profile_id that no registered log profile has
+    UnknownProfile(String),
+    /// A log on a network share that could not be reached (retrying may succeed)
+    Unavailable,
+    /// An upload that couldn't reach its endpoint (connection refused, timed out, ...)
+    Network(String),
+    /// An upload endpoint that answered with a non-success HTTP status
+    Rejected(u16),
+    /// A file the app may not delete: outside the granted folders, or not a log
+    OutsideScope,
+    /// A log that is being watched and can't be deleted
+    InUse,
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogError::NotFound => write!(f, "Log file not found"),
+            LogError::PermissionDenied => write!(f, "Permission denied reading log file"),
+            LogError::NotAFile => write!(f, "Path is a folder, not a log file"),
+            LogError::Io(message) => write!(f, "I/O error: {}", message),
+            LogError::UnknownProfile(id) => write!(f, "Unknown log profile: {}", id),
+            LogError::Unavailable => write!(f, "Log file is on a network share that can't be reached"),
+            LogError::Network(message) => write!(f, "Network error: {}", message),
+            LogError::Rejected(status) => write!(f, "Upload rejected with HTTP status {}", status),
+            LogError::OutsideScope => write!(f, "Only log files in a granted folder can be deleted"),
+            LogError::InUse => write!(f, "Log file is being watched"),
+        }
+    }
+}
+
+impl std::error::Error for LogError {}
+
+impl From<io::Error> for LogError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::NotFound => LogError::NotFound,
+            io::ErrorKind::PermissionDenied => LogError::PermissionDenied,
+            _ => LogError::Io(e.to_string()),
+        }
+    }
+}
+
+impl From<notify::Error> for LogError {
+    fn from(e: notify::Error) -> Self {
+        match e.kind {
+            notify::ErrorKind::Io(io_error) => io_error.into(),
+            notify::ErrorKind::PathNotFound => LogError::NotFound,
+            _ => LogError::Io(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_io_error_kinds() {
+        let not_found = io::Error::new(io::ErrorKind::NotFound, "missing");
+        let denied = io::Error::new(io::ErrorKind::PermissionDenied, "denied");
+        let other = io::Error::other("disk on fire");
+
+        assert_eq!(LogError::from(not_found), LogError::NotFound);
+        assert_eq!(LogError::from(denied), LogError::PermissionDenied);
+        assert_eq!(LogError::from(other), LogError::Io("disk on fire".to_string()));
+    }
+
+    #[test]
+    fn test_serialization_shape() {
+        assert_eq!(
+            serde_json::to_value(LogError::NotFound).unwrap(),
+            serde_json::json!({ "kind": "not_found" })
+        );
+        assert_eq!(
+            serde_json::to_value(LogError::Io("boom".to_string())).unwrap(),
+            serde_json::json!({ "kind": "io", "details": "boom" })
+        );
+    }
+}
@@ -0,0 +1,821 @@
+//! Structured events built from marker-matched log lines
+//!
+//! The marker pre-filter in lib.rs decides *whether* a line is interesting; this module
+//! decides *what* it is, so the frontend receives typed events instead of re-parsing
+//! raw strings on the UI thread.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tauri::ipc::Channel;
+
+use crate::error::LogError;
+use crate::{markers, parsers};
+use crate::{decode_line, for_each_line, open_log_reader, read_raw_line, ReadCancellation, SEVERITY_RE};
+
+/// A marker-matched log line mapped to an event type
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct ParsedEvent {
+    /// ISO timestamp as written in the log (empty if the line has none)
+    pub timestamp: String,
+    /// `timestamp` as Unix epoch milliseconds
+    pub timestamp_ms: Option<i64>,
+    pub event_type: String,
+    pub raw: String,
+    pub attributes: HashMap<String, String>,
+    /// World coordinates, for event types that log where they happened
+    pub position: Option<parsers::Vec3>,
+}
+
+/// Event type for each entry in EVENT_MARKERS, checked in order (first match wins)
+pub const MARKER_EVENT_TYPES: &[(&str, &str)] = &[
+    // Connection events
+    ("AccountLoginCharacterStatus_Character", "player_login"),
+
+    // Inventory events
+    ("<RequestLocationInventory>", "location_inventory"),
+    ("<EquipItem>", "equip_item"),
+    ("<AttachmentReceived>", "attachment_received"),
+
+    // Vehicle events
+    ("<Vehicle Control Flow>", "vehicle_control"),
+    ("<[ActorState] Place>", "actor_place"),
+    ("<Quantum Drive Arrived", "quantum_arrived"),
+    ("<Jump Drive Requesting State Change>", "jump_drive_state"),
+    ("Destruction>", "destruction"),
+
+    // Combat events
+    ("<Actor Death>", "actor_death"),
+    ("<[ActorState] Dead>", "actor_dead"),
+    ("<FatalCollision>", "fatal_collision"),
+    ("<[STAMINA]", "stamina"),
+
+    // Mission events
+    ("<MissionShared>", "mission_shared"),
+    ("<ObjectiveUpserted>", "objective_upserted"),
+    ("<MissionEnded>", "mission_ended"),
+    ("<EndMission>", "end_mission"),
+    ("<CLocalMissionPhaseMarker::CreateMarker>", "mission_marker"),
+
+    // Economy events
+    ("<CEntityComponentShoppingProvider::SendStandardItemBuyRequest>", "item_purchase"),
+    ("<CWallet::ProcessClaimToNextStep>", "wallet_claim"),
+
+    // Location events
+    ("<CLandingArea::OnDoorOpenStateChanged>", "landing_area"),
+    ("<CSCItemDockingTube::OnSetTubeState>", "docking_tube"),
+    ("<CSCLoadingPlatformManager>", "loading_platform"),
+    ("<Spawn Flow>", "spawn"),
+    ("<CEntity::OnOwnerRemoved>", "owner_removed"),
+
+    // System events
+    ("<SystemQuit>", "system_quit"),
+    ("<Failed to get starmap route data!>", "starmap_error"),
+];
+
+/// Event types whose lines may carry world coordinates
+const POSITION_EVENT_TYPES: &[&str] = &["actor_death", "actor_dead", "fatal_collision", "destruction", "spawn"];
+
+/// Map a line to its event type via the marker table
+pub fn event_type_for_line(line: &str) -> Option<&'static str> {
+    MARKER_EVENT_TYPES
+        .iter()
+        .find(|(marker, _)| line.contains(marker))
+        .map(|(_, event_type)| *event_type)
+}
+
+/// Event type of a marker-matched line, resolved the same way as in parse_event
+pub fn classify_line(line: &str) -> String {
+    match event_type_for_line(line) {
+        Some(event_type) => event_type.to_string(),
+        None => markers::find_rule(line).map_or_else(|| "custom".to_string(), |m| m.event_type),
+    }
+}
+
+/// Leading `<...>` timestamp of a line, without the angle brackets
+pub fn extract_timestamp(line: &str) -> Option<&str> {
+    crate::TIMESTAMP_RE
+        .find(line)
+        .map(|m| m.as_str().trim_end().trim_start_matches('<').trim_end_matches('>'))
+}
+
+/// Leading timestamp of a line as Unix epoch milliseconds, parsed by the Star Citizen
+/// profile. Lines without a leading timestamp yield None.
+pub fn parse_timestamp(line: &str) -> Option<i64> {
+    crate::profiles::default_profile().parse_timestamp(line)
+}
+
+/// Insert an optional value into an attribute map, skipping None
+fn insert_attribute(attributes: &mut HashMap<String, String>, key: &str, value: Option<String>) {
+    if let Some(value) = value {
+        attributes.insert(key.to_string(), value);
+    }
+}
+
+/// Populate event-specific attributes from the structured parsers.
+/// Returns false if the type has a parser and it rejected the line (format drift).
+fn add_structured_attributes(event_type: &str, line: &str, attributes: &mut HashMap<String, String>) -> bool {
+    match event_type {
+        "player_login" => {
+            let Some(name) = crate::extract_player_name(line, &crate::player_name_config()) else {
+                return false;
+            };
+            insert_attribute(attributes, "player_name", Some(name));
+        }
+        "actor_death" => {
+            let Some(death) = parsers::parse_actor_death(line) else {
+                return false;
+            };
+            insert_attribute(attributes, "victim", Some(death.victim));
+            insert_attribute(attributes, "victim_geid", Some(death.victim_geid));
+            insert_attribute(attributes, "killer", death.killer);
+            insert_attribute(attributes, "killer_geid", death.killer_geid);
+            insert_attribute(attributes, "weapon", death.weapon);
+            insert_attribute(attributes, "damage_type", death.damage_type);
+            insert_attribute(attributes, "zone", death.zone);
+        }
+        "equip_item" | "attachment_received" => {
+            let Some(item) = parsers::parse_inventory_event(line) else {
+                return false;
+            };
+            insert_attribute(attributes, "item_class", Some(item.item_class));
+            insert_attribute(attributes, "item_name", Some(item.display_name));
+            insert_attribute(attributes, "slot", item.slot);
+            insert_attribute(attributes, "port", item.port);
+            insert_attribute(attributes, "state", item.state);
+        }
+        "vehicle_control" => {
+            // Seat requests and default seats are skipped by design, not parse failures
+            if let Some(vehicle) = parsers::parse_vehicle_control(line) {
+                let action = match vehicle.action {
+                    parsers::VehicleAction::Enter => "enter",
+                    parsers::VehicleAction::Exit => "exit",
+                };
+                insert_attribute(attributes, "action", Some(action.to_string()));
+                insert_attribute(attributes, "vehicle_class", Some(vehicle.vehicle_class));
+                insert_attribute(attributes, "vehicle_geid", Some(vehicle.vehicle_geid));
+                insert_attribute(attributes, "seat", vehicle.seat);
+            }
+        }
+        "quantum_arrived" => {
+            let Some(arrival) = parsers::parse_quantum_arrival(line) else {
+                return false;
+            };
+            insert_attribute(attributes, "vehicle_class", Some(arrival.vehicle_class));
+            insert_attribute(attributes, "vehicle_geid", Some(arrival.vehicle_geid));
+        }
+        "mission_shared" | "objective_upserted" | "mission_ended" | "end_mission" | "mission_marker" => {
+            let Some(mission) = parsers::parse_mission_event(line) else {
+                return false;
+            };
+            insert_attribute(attributes, "mission_id", Some(mission.mission_id));
+            insert_attribute(attributes, "mission_name", mission.mission_name);
+            insert_attribute(attributes, "objective", mission.objective);
+            insert_attribute(attributes, "state", mission.state);
+        }
+        "landing_area" | "docking_tube" => {
+            let Some(location) = parsers::parse_location_event(line) else {
+                return false;
+            };
+            insert_attribute(attributes, "location_name", location.location_name);
+            insert_attribute(attributes, "state", location.state);
+        }
+        "item_purchase" | "wallet_claim" => {
+            let Some(economy) = parsers::parse_economy_event(line) else {
+                return false;
+            };
+            insert_attribute(attributes, "item_or_reason", Some(economy.item_or_reason));
+            insert_attribute(attributes, "amount", economy.amount.map(|amount| amount.to_string()));
+            insert_attribute(attributes, "shop", economy.shop);
+        }
+        "loading_platform" => {
+            let Some(platform) = parsers::parse_platform_event(line) else {
+                return false;
+            };
+            insert_attribute(attributes, "platform_id", Some(platform.platform_id));
+            insert_attribute(attributes, "state", Some(platform.state));
+        }
+        "fatal_collision" => {
+            let Some(collision) = parsers::parse_fatal_collision(line) else {
+                return false;
+            };
+            insert_attribute(attributes, "entity_a", Some(collision.entity_a));
+            insert_attribute(attributes, "entity_b", Some(collision.entity_b));
+            insert_attribute(attributes, "speed", collision.speed.map(|speed| speed.to_string()));
+            insert_attribute(attributes, "zone", collision.zone);
+            insert_attribute(attributes, "hit_environment", Some(collision.hit_environment.to_string()));
+        }
+        "jump_drive_state" => {
+            let Some(state) = parsers::parse_jump_drive_state(line) else {
+                return false;
+            };
+            insert_attribute(attributes, "from_state", Some(state.from_state));
+            insert_attribute(attributes, "to_state", Some(state.to_state));
+            insert_attribute(attributes, "state", Some(state.state));
+            insert_attribute(attributes, "reason", Some(state.reason));
+            insert_attribute(attributes, "vehicle", state.vehicle);
+            insert_attribute(attributes, "zone", state.zone);
+        }
+        _ => {}
+    }
+    true
+}
+
+/// A line of a built-in event type whose structured parser rejected it
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct UnparsedEvent {
+    pub event_type: String,
+    pub line_number: usize,
+    pub raw: String,
+}
+
+/// Report a marker-matched line whose structured parser failed, usually because
+/// a patch changed the line's format
+pub fn check_unparsed(line: &str, line_number: usize) -> Option<UnparsedEvent> {
+    let event_type = event_type_for_line(line)?;
+    if add_structured_attributes(event_type, line, &mut HashMap::new()) {
+        return None;
+    }
+    Some(UnparsedEvent {
+        event_type: event_type.to_string(),
+        line_number,
+        raw: line.to_string(),
+    })
+}
+
+/// Build a structured event from a log line, or None if no active marker matches.
+/// Built-in types take precedence, then marker rules (whose named regex groups become
+/// attributes); anything else matched by a custom marker gets the type "custom".
+pub fn parse_event(line: &str) -> Option<ParsedEvent> {
+    if !crate::contains_event_marker(line) {
+        return None;
+    }
+
+    let mut attributes = HashMap::new();
+    let event_type = match event_type_for_line(line) {
+        Some(event_type) => event_type.to_string(),
+        None => match markers::find_rule(line) {
+            Some(rule_match) => {
+                attributes.extend(rule_match.captures);
+                rule_match.event_type
+            }
+            None => "custom".to_string(),
+        },
+    };
+
+    if let Some(severity) = SEVERITY_RE.captures(line) {
+        attributes.insert("severity".to_string(), severity[1].to_string());
+    }
+    add_structured_attributes(&event_type, line, &mut attributes);
+    markers::extract_fields(&event_type, line, &mut attributes);
+    let position = if POSITION_EVENT_TYPES.contains(&event_type.as_str()) {
+        parsers::parse_coordinates(line)
+    } else {
+        None
+    };
+
+    Some(ParsedEvent {
+        timestamp: extract_timestamp(line).unwrap_or_default().to_string(),
+        timestamp_ms: parse_timestamp(line),
+        event_type,
+        raw: line.to_string(),
+        attributes,
+        position,
+    })
+}
+
+/// Read marker-matched lines from `from_line` onward as structured events
+#[tauri::command]
+pub fn read_parsed_events(path: &str, from_line: usize) -> Result<Vec<ParsedEvent>, LogError> {
+    let mut events = Vec::new();
+    for_each_line(path, from_line, |_, line| events.extend(parse_event(line)))?;
+    Ok(events)
+}
+
+/// Inventory changes (equips and attachments) from `from_line` onward, for a loadout tracker
+#[tauri::command]
+pub fn read_inventory_events(path: &str, from_line: usize) -> Result<Vec<parsers::InventoryEvent>, LogError> {
+    let mut events = Vec::new();
+    for_each_line(path, from_line, |_, line| events.extend(parsers::parse_inventory_event(line)))?;
+    Ok(events)
+}
+
+/// Count of each event type from `from_line` onward, for a session breakdown chart.
+/// Lines are classified exactly like parse_event, so totals match the event list.
+#[tauri::command]
+pub fn event_type_histogram(path: &str, from_line: usize) -> Result<HashMap<String, usize>, LogError> {
+    let mut counts = HashMap::new();
+    for_each_line(path, from_line, |_, line| {
+        if crate::contains_event_marker(line) {
+            *counts.entry(classify_line(line)).or_insert(0) += 1;
+        }
+    })?;
+    Ok(counts)
+}
+
+/// Epoch ms of the latest occurrence of each event type from `from_line` onward, for a
+/// "systems active" dashboard. Types never seen (or only on untimestamped lines) are omitted.
+#[tauri::command]
+pub fn marker_last_seen(path: &str, from_line: usize) -> Result<HashMap<String, i64>, LogError> {
+    let mut last_seen = HashMap::new();
+    for_each_line(path, from_line, |_, line| {
+        if crate::contains_event_marker(line) {
+            if let Some(ts) = parse_timestamp(line) {
+                // Later lines win, even if SC logged them with a slightly older clock
+                last_seen.insert(classify_line(line), ts);
+            }
+        }
+    })?;
+    Ok(last_seen)
+}
+
+/// Spawns and despawns of the logged-in player from `from_line` onward, for a presence
+/// timeline. Ownership churn of other entities is dropped once the login is known.
+#[tauri::command]
+pub fn read_presence_events(path: &str, from_line: usize) -> Result<Vec<parsers::PresenceEvent>, LogError> {
+    let config = crate::player_name_config();
+    let mut events = Vec::new();
+    let mut player_name = None;
+
+    // Read from the top: the login may precede from_line
+    for_each_line(path, 0, |line_number, line| {
+        if let Some(name) = crate::extract_player_name(line, &config) {
+            player_name = Some(name);
+        }
+        if line_number >= from_line {
+            if let Some(event) = parsers::parse_presence_event(line) {
+                if event.is_player(player_name.as_deref()) {
+                    events.push(event);
+                }
+            }
+        }
+    })?;
+    Ok(events)
+}
+
+/// Actor deaths involving one player, split by their side of the kill
+#[derive(serde::Serialize, Debug, Default, PartialEq)]
+pub struct PlayerCombat {
+    /// The player killed someone else
+    pub kills: Vec<parsers::ActorDeath>,
+    /// The player died, suicides included
+    pub deaths: Vec<parsers::ActorDeath>,
+}
+
+impl PlayerCombat {
+    /// File `death` under kills or deaths if `player` (compared case-insensitively) took part
+    fn push(&mut self, death: parsers::ActorDeath, player: &str) {
+        let is_player = |name: &str| parsers::is_same_player(name, player);
+        if is_player(&death.victim) {
+            self.deaths.push(death);
+        } else if death.killer.as_deref().is_some_and(is_player) {
+            self.kills.push(death);
+        }
+    }
+}
+
+/// The player's own kills and deaths from `from_line` onward, for a personal kill feed.
+/// Without `player_name` the character logged in at each point of the log is used.
+/// Deaths logged twice are dropped as in compute_stats.
+#[tauri::command]
+pub fn read_player_combat(
+    path: &str,
+    from_line: usize,
+    player_name: Option<String>,
+    dedup_window_ms: Option<i64>,
+) -> Result<PlayerCombat, LogError> {
+    let config = crate::player_name_config();
+    let mut deaths = parsers::DeathDeduper::new(dedup_window_ms.unwrap_or(parsers::DEFAULT_DEATH_DEDUP_WINDOW_MS));
+    let mut combat = PlayerCombat::default();
+    let mut detected_name = None;
+
+    // Read from the top: the login may precede from_line
+    for_each_line(path, 0, |line_number, line| {
+        if player_name.is_none() {
+            if let Some(name) = crate::extract_player_name(line, &config) {
+                detected_name = Some(name);
+            }
+        }
+        if line_number >= from_line {
+            let player = player_name.as_deref().or(detected_name.as_deref());
+            if let (Some(player), Some(death)) = (player, parsers::parse_actor_death(line)) {
+                if !deaths.is_duplicate(&death, parse_timestamp(line)) {
+                    combat.push(death, player);
+                }
+            }
+        }
+    })?;
+    Ok(combat)
+}
+
+/// Elevator and loading platform state changes from `from_line` onward, for the
+/// station timeline. A state repeated by the same platform is only reported once.
+#[tauri::command]
+pub fn read_platform_events(path: &str, from_line: usize) -> Result<Vec<parsers::PlatformEvent>, LogError> {
+    let mut events = Vec::new();
+    let mut last_states: HashMap<String, String> = HashMap::new();
+    for_each_line(path, from_line, |_, line| {
+        if let Some(event) = parsers::parse_platform_event(line) {
+            let previous = last_states.insert(event.platform_id.clone(), event.state.clone());
+            if previous.as_deref() != Some(event.state.as_str()) {
+                events.push(event);
+            }
+        }
+    })?;
+    Ok(events)
+}
+
+/// Purchases and insurance claims from `from_line` onward, for a spend tracker
+#[tauri::command]
+pub fn read_economy_events(path: &str, from_line: usize) -> Result<Vec<parsers::EconomyEvent>, LogError> {
+    let mut events = Vec::new();
+    for_each_line(path, from_line, |_, line| events.extend(parsers::parse_economy_event(line)))?;
+    Ok(events)
+}
+
+/// How read_stamina_series thins out its samples
+#[derive(serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Downsample {
+    /// Keep the first of every N samples
+    EveryNth(usize),
+    /// Keep the first sample of each second
+    PerSecond,
+}
+
+/// Drop samples according to `downsample`, keeping the first of each group
+fn downsample_stamina(samples: Vec<parsers::StaminaSample>, downsample: Downsample) -> Vec<parsers::StaminaSample> {
+    match downsample {
+        Downsample::EveryNth(n) => samples.into_iter().step_by(n.max(1)).collect(),
+        Downsample::PerSecond => {
+            let mut last_second = None;
+            samples
+                .into_iter()
+                .filter(|sample| {
+                    let second = sample.timestamp_ms.div_euclid(1000);
+                    last_second.replace(second) != Some(second)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Stamina samples from `from_line` onward, for a combat-intensity graph.
+/// They fire many times a second in fights, so pass `downsample` to thin them out.
+#[tauri::command]
+pub fn read_stamina_series(
+    path: &str,
+    from_line: usize,
+    downsample: Option<Downsample>,
+) -> Result<Vec<parsers::StaminaSample>, LogError> {
+    let mut samples = Vec::new();
+    for_each_line(path, from_line, |_, line| samples.extend(parsers::parse_stamina(line)))?;
+
+    Ok(match downsample {
+        Some(downsample) => downsample_stamina(samples, downsample),
+        None => samples,
+    })
+}
+
+/// Message sent over the stream_log_events channel
+#[derive(serde::Serialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StreamMessage {
+    Events { events: Vec<ParsedEvent> },
+    Done { line_count: usize, cancelled: bool },
+}
+
+/// Number of events per channel message
+const STREAM_BATCH_SIZE: usize = 200;
+
+/// Stream parsed events from `from_line` onward over a channel in batches, so a
+/// multi-hundred-MB catch-up read renders progressively instead of arriving as one
+/// giant payload. Ends with a `done` message carrying the total line count.
+/// Pass a unique `token` to make the stream abortable via cancel_read.
+#[tauri::command(async)]
+pub fn stream_log_events(
+    path: String,
+    from_line: usize,
+    channel: Channel<StreamMessage>,
+    token: Option<String>,
+) -> Result<(), LogError> {
+    let cancellation = ReadCancellation::new(token);
+    let mut reader = open_log_reader(&path)?;
+
+    let send = |message: StreamMessage| {
+        channel
+            .send(message)
+            .map_err(|e| LogError::Io(format!("Failed to send events: {}", e)))
+    };
+
+    let mut batch = Vec::with_capacity(STREAM_BATCH_SIZE);
+    let mut buf = Vec::new();
+    let mut line_count = 0usize;
+    let mut cancelled = false;
+
+    loop {
+        if cancellation.should_stop(line_count) {
+            cancelled = true;
+            break;
+        }
+        if read_raw_line(&mut reader, &mut buf)? == 0 {
+            break;
+        }
+        if line_count >= from_line {
+            if let Some(event) = parse_event(&decode_line(&buf)) {
+                batch.push(event);
+                if batch.len() == STREAM_BATCH_SIZE {
+                    send(StreamMessage::Events {
+                        events: std::mem::take(&mut batch),
+                    })?;
+                }
+            }
+        }
+        line_count += 1;
+    }
+
+    if !batch.is_empty() {
+        send(StreamMessage::Events { events: batch })?;
+    }
+    send(StreamMessage::Done {
+        line_count,
+        cancelled,
+    })
+}
+
+/// Message sent over the replay_log channel
+#[derive(serde::Serialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReplayMessage {
+    Event { event: ParsedEvent },
+    Done { events: usize, cancelled: bool },
+}
+
+/// Slowest and fastest replay speeds (multiples of real time)
+const MIN_REPLAY_SPEED: f64 = 0.1;
+const MAX_REPLAY_SPEED: f64 = 1000.0;
+
+/// How often a replay waiting for its next event checks for cancellation
+const REPLAY_CANCEL_CHECK: Duration = Duration::from_millis(100);
+
+/// Time to wait between events logged at `previous_ms` and `current_ms` when replaying
+/// at `speed`. Events without a timestamp, or logged out of order, follow at once.
+fn replay_delay(previous_ms: Option<i64>, current_ms: Option<i64>, speed: f64) -> Duration {
+    match (previous_ms, current_ms) {
+        (Some(previous), Some(current)) if current > previous => {
+            let speed = speed.clamp(MIN_REPLAY_SPEED, MAX_REPLAY_SPEED);
+            Duration::from_secs_f64((current - previous) as f64 / 1000.0 / speed)
+        }
+        _ => Duration::ZERO,
+    }
+}
+
+/// Sleep for `delay` in short steps, returning false as soon as the replay is cancelled
+fn wait_unless_cancelled(delay: Duration, cancellation: &ReadCancellation) -> bool {
+    let deadline = Instant::now() + delay;
+    loop {
+        if cancellation.is_cancelled() {
+            return false;
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return true;
+        }
+        std::thread::sleep(remaining.min(REPLAY_CANCEL_CHECK));
+    }
+}
+
+/// Re-emit the events of a log over a channel as if it were being written live, spaced
+/// by their timestamps divided by `speed` (clamped to 0.1x-1000x), for demos and for
+/// checking how the kill feed renders. Ends with a `done` message.
+/// Pass a unique `token` to make the replay abortable via cancel_read.
+#[tauri::command(async)]
+pub fn replay_log(
+    path: String,
+    speed: f64,
+    channel: Channel<ReplayMessage>,
+    token: Option<String>,
+) -> Result<(), LogError> {
+    let cancellation = ReadCancellation::new(token);
+    let mut reader = open_log_reader(&path)?;
+
+    let send = |message: ReplayMessage| {
+        channel
+            .send(message)
+            .map_err(|e| LogError::Io(format!("Failed to send events: {}", e)))
+    };
+
+    let mut buf = Vec::new();
+    let mut previous_ms = None;
+    let mut events = 0usize;
+    let mut cancelled = false;
+
+    while read_raw_line(&mut reader, &mut buf)? > 0 {
+        let Some(event) = parse_event(&decode_line(&buf)) else {
+            continue;
+        };
+        if !wait_unless_cancelled(replay_delay(previous_ms, event.timestamp_ms, speed), &cancellation) {
+            cancelled = true;
+            break;
+        }
+        previous_ms = event.timestamp_ms.or(previous_ms);
+        send(ReplayMessage::Event { event })?;
+        events += 1;
+    }
+
+    send(ReplayMessage::Done { events, cancelled })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_event_marker_has_a_type() {
+        for marker in crate::EVENT_MARKERS {
+            assert!(
+                MARKER_EVENT_TYPES.iter().any(|(m, _)| m == marker),
+                "Marker {} has no event type",
+                marker
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_event_maps_marker_to_type() {
+        let line = "<2024-01-01T12:00:00.000Z> [Notice] <Actor Death> CActor::Kill: 'Victim' [1] in zone 'zone' killed by 'Killer' [2] using 'weapon' with damage type 'Combat'";
+        let event = parse_event(line).unwrap();
+
+        assert_eq!(event.event_type, "actor_death");
+        assert_eq!(event.attributes.get("killer"), Some(&"Killer".to_string()));
+        assert_eq!(event.timestamp, "2024-01-01T12:00:00.000Z");
+        assert_eq!(event.timestamp_ms, Some(1_704_110_400_000));
+        assert_eq!(event.attributes.get("severity"), Some(&"Notice".to_string()));
+        assert_eq!(event.raw, line);
+    }
+
+    #[test]
+    fn test_parse_event_player_login_attribute() {
+        let line = "<2024-01-01T12:00:00.000Z> AccountLoginCharacterStatus_Character - name Some-Pilot - state 1";
+        let event = parse_event(line).unwrap();
+
+        assert_eq!(event.event_type, "player_login");
+        assert_eq!(event.attributes.get("player_name"), Some(&"Some-Pilot".to_string()));
+    }
+
+    #[test]
+    fn test_parse_event_position() {
+        let line = "<2024-01-01T12:00:00.000Z> <FatalCollision> Fatal Collision occured for vehicle AEGS_Gladius_1 at pos x: -1.5e+06, y: 20, z: 3.25";
+        let event = parse_event(line).unwrap();
+        assert_eq!(event.position, Some(parsers::Vec3 { x: -1.5e6, y: 20.0, z: 3.25 }));
+
+        // Coordinates are only attached to location-bearing event types
+        let quit = "<2024-01-01T12:00:00.000Z> <SystemQuit> x: 1, y: 2, z: 3";
+        assert_eq!(parse_event(quit).unwrap().position, None);
+    }
+
+    #[test]
+    fn test_parse_timestamp_fractional_seconds() {
+        let line = "<2024-01-01T12:00:01.250Z> [Notice] message";
+        assert_eq!(parse_timestamp(line), Some(1_704_110_401_250));
+    }
+
+    #[test]
+    fn test_parse_timestamp_missing() {
+        assert_eq!(parse_timestamp("[Notice] no timestamp here"), None);
+        assert_eq!(parse_timestamp(""), None);
+    }
+
+    #[test]
+    fn test_stream_log_events_batches_and_done() {
+        use std::sync::{Arc, Mutex};
+        use tauri::ipc::InvokeResponseBody;
+
+        let mut content = String::new();
+        for i in 0..(STREAM_BATCH_SIZE + 5) {
+            content.push_str(&format!("<2024-01-01T12:00:00.000Z> <SystemQuit> quit {}\n", i));
+            content.push_str("noise\n");
+        }
+        let path = crate::tests::write_temp_log("stream", &content);
+
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let sink = messages.clone();
+        let channel = Channel::new(move |body| {
+            if let InvokeResponseBody::Json(json) = body {
+                sink.lock().unwrap().push(serde_json::from_str::<serde_json::Value>(&json).unwrap());
+            }
+            Ok(())
+        });
+
+        stream_log_events(path.to_str().unwrap().to_string(), 0, channel, None).unwrap();
+
+        let messages = messages.lock().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0]["kind"], "events");
+        assert_eq!(messages[0]["events"].as_array().unwrap().len(), STREAM_BATCH_SIZE);
+        assert_eq!(messages[1]["events"].as_array().unwrap().len(), 5);
+        assert_eq!(messages[2]["kind"], "done");
+        assert_eq!(messages[2]["line_count"], (STREAM_BATCH_SIZE + 5) * 2);
+        assert_eq!(messages[2]["cancelled"], false);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_event_no_marker() {
+        assert!(parse_event("<2024-01-01T12:00:00.000Z> nothing interesting").is_none());
+    }
+
+    #[test]
+    fn test_downsample_stamina() {
+        let sample = |timestamp_ms| parsers::StaminaSample { timestamp_ms, value: None };
+        let samples: Vec<_> = [1000, 1200, 1900, 2100, 2500, 4000].into_iter().map(sample).collect();
+
+        let per_second = downsample_stamina(samples.clone(), Downsample::PerSecond);
+        let times: Vec<i64> = per_second.iter().map(|s| s.timestamp_ms).collect();
+        assert_eq!(times, vec![1000, 2100, 4000]);
+
+        let every_third = downsample_stamina(samples, Downsample::EveryNth(3));
+        let times: Vec<i64> = every_third.iter().map(|s| s.timestamp_ms).collect();
+        assert_eq!(times, vec![1000, 2100]);
+
+        let json: Downsample = serde_json::from_value(serde_json::json!({ "every_nth": 5 })).unwrap();
+        assert_eq!(json, Downsample::EveryNth(5));
+    }
+
+    #[test]
+    fn test_event_type_histogram_matches_parsed_events() {
+        let path = crate::tests::write_temp_log(
+            "histogram",
+            "<2024-01-01T12:00:00.000Z> [Notice] <Actor Death> CActor::Kill: 'Victim' [1] in zone 'zone' killed by 'Killer' [2] using 'weapon' with damage type 'Combat'\n\
+             <2024-01-01T12:00:01.000Z> [Notice] <Actor Death> format changed in a patch\n\
+             <2024-01-01T12:00:02.000Z> [Notice] <SystemQuit> bye\n\
+             <2024-01-01T12:00:03.000Z> [Notice] plain line\n",
+        );
+        let path_str = path.to_str().unwrap();
+
+        let histogram = event_type_histogram(path_str, 0).unwrap();
+        assert_eq!(histogram.get("actor_death"), Some(&2));
+        assert_eq!(histogram.values().sum::<usize>(), read_parsed_events(path_str, 0).unwrap().len());
+        assert_eq!(event_type_histogram(path_str, 2).unwrap().get("actor_death"), None);
+
+        let last_seen = marker_last_seen(path_str, 0).unwrap();
+        assert_eq!(last_seen.get("actor_death"), Some(&1_704_110_401_000));
+        assert_eq!(last_seen.get("system_quit"), Some(&1_704_110_402_000));
+        assert_eq!(last_seen.len(), 2);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_player_combat() {
+        let path = crate::tests::write_temp_log(
+            "player_combat",
+            "<2024-01-01T12:00:00.000Z> AccountLoginCharacterStatus_Character - name Pilot - state 1\n\
+             <2024-01-01T12:01:00.000Z> <Actor Death> CActor::Kill: 'Enemy' [2] in zone 'z' killed by 'PILOT' [1] using 'gun' with damage type 'Bullet'\n\
+             <2024-01-01T12:02:00.000Z> <Actor Death> CActor::Kill: 'Npc' [3] in zone 'z' killed by 'Enemy' [2] using 'gun' with damage type 'Bullet'\n\
+             <2024-01-01T12:03:00.000Z> <Actor Death> CActor::Kill: 'Pilot' [1] in zone 'z' killed by 'Pilot' [1] using 'gun' with damage type 'Suicide'\n",
+        );
+        let path_str = path.to_str().unwrap();
+
+        let combat = read_player_combat(path_str, 0, None, None).unwrap();
+        assert_eq!(combat.kills.len(), 1);
+        assert_eq!(combat.kills[0].victim, "Enemy");
+        assert_eq!(combat.deaths.len(), 1);
+        assert!(combat.deaths[0].suicide);
+
+        let enemy = read_player_combat(path_str, 1, Some("enemy".to_string()), None).unwrap();
+        assert_eq!(enemy.kills.len(), 1);
+        assert_eq!(enemy.deaths.len(), 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_platform_events_groups_repeated_states() {
+        let path = crate::tests::write_temp_log(
+            "platforms",
+            "<2024-01-01T12:00:00.000Z> <CSCLoadingPlatformManager> platform[Lift_A] state[Moving]\n\
+             <2024-01-01T12:00:01.000Z> <CSCLoadingPlatformManager> platform[Lift_B] state[Moving]\n\
+             <2024-01-01T12:00:02.000Z> <CSCLoadingPlatformManager> platform[Lift_A] state[Moving]\n\
+             <2024-01-01T12:00:03.000Z> <CSCLoadingPlatformManager> platform[Lift_A] state[Arrived]\n",
+        );
+
+        let events = read_platform_events(path.to_str().unwrap(), 0).unwrap();
+        let states: Vec<_> = events.iter().map(|e| (e.platform_id.as_str(), e.state.as_str())).collect();
+        assert_eq!(states, vec![("Lift_A", "Moving"), ("Lift_B", "Moving"), ("Lift_A", "Arrived")]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_delay() {
+        assert_eq!(replay_delay(Some(1_000), Some(3_000), 1.0), Duration::from_secs(2));
+        assert_eq!(replay_delay(Some(1_000), Some(3_000), 4.0), Duration::from_millis(500));
+        // Speeds are clamped, and gaps without two timestamps are skipped
+        assert_eq!(replay_delay(Some(0), Some(1_000), 1e9), Duration::from_millis(1));
+        assert_eq!(replay_delay(Some(0), Some(1_000), 0.0), Duration::from_secs(10));
+        assert_eq!(replay_delay(None, Some(1_000), 1.0), Duration::ZERO);
+        assert_eq!(replay_delay(Some(5_000), Some(1_000), 1.0), Duration::ZERO);
+    }
+}
@@ -0,0 +1,318 @@
+//! Exporting parsed events to files the player can share or open in a spreadsheet
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::LogError;
+use crate::events::{parse_event, read_parsed_events, ParsedEvent};
+use crate::stats::{compute_stats, SessionStats};
+use crate::{decode_line, get_log_metadata, header, open_log_reader, read_raw_line, TIMESTAMP_RE};
+
+lazy_static! {
+    // GEIDs, account ids and other long numeric ids; shorter numbers (counts,
+    // coordinates, versions) carry no identity and are kept for debugging
+    static ref LONG_ID_RE: Regex = Regex::new(r"\b\d{7,}\b").unwrap();
+}
+
+/// Bumped whenever the layout of the session JSON export changes
+pub const SESSION_SCHEMA_VERSION: u32 = 1;
+
+/// Attributes given their own CSV column; everything else lands in `other_attributes`
+const CSV_ATTRIBUTE_COLUMNS: &[&str] = &[
+    "severity",
+    "victim",
+    "killer",
+    "weapon",
+    "damage_type",
+    "zone",
+    "vehicle_class",
+    "item_name",
+    "mission_name",
+];
+
+/// Quote a CSV field if it contains a delimiter, quote or line break (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn write_csv_row<W: Write>(out: &mut W, fields: &[&str]) -> std::io::Result<()> {
+    let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+    writeln!(out, "{}", row.join(","))
+}
+
+/// Write events from `path` to a CSV at `out_path`, one row per event, optionally
+/// limited to `event_types`. Rows are streamed as the log is read. Returns the
+/// number of rows written.
+#[tauri::command]
+pub fn export_events_csv(
+    path: &str,
+    out_path: &str,
+    event_types: Option<Vec<String>>,
+) -> Result<usize, LogError> {
+    let mut reader = open_log_reader(path)?;
+    let mut out = BufWriter::new(File::create(out_path)?);
+
+    let mut header = vec!["timestamp", "event_type"];
+    header.extend_from_slice(CSV_ATTRIBUTE_COLUMNS);
+    header.push("other_attributes");
+    write_csv_row(&mut out, &header)?;
+
+    let mut rows = 0;
+    let mut buf = Vec::new();
+    while read_raw_line(&mut reader, &mut buf)? > 0 {
+        let Some(event) = parse_event(&decode_line(&buf)) else {
+            continue;
+        };
+        if event_types
+            .as_ref()
+            .is_some_and(|types| !types.contains(&event.event_type))
+        {
+            continue;
+        }
+
+        let mut other: Vec<String> = event
+            .attributes
+            .iter()
+            .filter(|(key, _)| !CSV_ATTRIBUTE_COLUMNS.contains(&key.as_str()))
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        other.sort();
+        let other = other.join("; ");
+
+        let mut fields = vec![event.timestamp.as_str(), event.event_type.as_str()];
+        fields.extend(
+            CSV_ATTRIBUTE_COLUMNS
+                .iter()
+                .map(|column| event.attributes.get(*column).map_or("", String::as_str)),
+        );
+        fields.push(&other);
+        write_csv_row(&mut out, &fields)?;
+        rows += 1;
+    }
+
+    out.flush()?;
+    Ok(rows)
+}
+
+/// Who, which build and how long, at the top of a session export
+#[derive(serde::Serialize)]
+pub struct SessionMetadata {
+    pub player_name: Option<String>,
+    pub game_version: Option<String>,
+    pub duration_ms: i64,
+    pub line_count: usize,
+}
+
+/// Document written by export_session_json
+#[derive(serde::Serialize)]
+pub struct SessionExport {
+    pub schema_version: u32,
+    pub metadata: SessionMetadata,
+    pub stats: SessionStats,
+    pub events: Vec<ParsedEvent>,
+}
+
+fn build_session_export(path: &str) -> Result<SessionExport, LogError> {
+    let log_metadata = get_log_metadata(path)?;
+    let stats = compute_stats(path, 0, None)?;
+
+    Ok(SessionExport {
+        schema_version: SESSION_SCHEMA_VERSION,
+        metadata: SessionMetadata {
+            player_name: log_metadata.player_name,
+            game_version: header::read_game_version(path)?,
+            duration_ms: stats.session_duration_ms,
+            line_count: log_metadata.line_count,
+        },
+        stats,
+        events: read_parsed_events(path, 0)?,
+    })
+}
+
+/// Write a versioned JSON summary of the session in `path` (metadata, stats and all
+/// parsed events) to `out_path`, for the web app and other tools to import
+#[tauri::command]
+pub fn export_session_json(path: &str, out_path: &str) -> Result<(), LogError> {
+    let export = build_session_export(path)?;
+    let mut out = BufWriter::new(File::create(out_path)?);
+    serde_json::to_writer_pretty(&mut out, &export).map_err(|e| LogError::Io(e.to_string()))?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Rewrites lines for sharing: literal redactions first, then long ids mapped to
+/// stable pseudonyms, leaving each line's timestamp untouched
+struct Anonymizer {
+    /// Text to replace and its replacement, longest first so a name isn't left
+    /// half-redacted by a shorter one it contains
+    redactions: Vec<(String, String)>,
+    pseudonyms: HashMap<String, String>,
+}
+
+impl Anonymizer {
+    fn new(mut redactions: Vec<(String, String)>) -> Self {
+        redactions.retain(|(from, _)| !from.is_empty());
+        redactions.sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+        Self {
+            redactions,
+            pseudonyms: HashMap::new(),
+        }
+    }
+
+    /// Same-width pseudonym for an id, the same one every time it appears
+    fn pseudonym(&mut self, id: &str) -> String {
+        let next = self.pseudonyms.len() + 1;
+        self.pseudonyms
+            .entry(id.to_string())
+            .or_insert_with(|| format!("{:0>width$}", next, width = id.len()))
+            .clone()
+    }
+
+    fn anonymize_line(&mut self, line: &str) -> String {
+        let content_start = TIMESTAMP_RE.find(line).map_or(0, |m| m.end());
+        let mut content = line[content_start..].to_string();
+        for (from, to) in &self.redactions {
+            content = content.replace(from.as_str(), to);
+        }
+        let content = LONG_ID_RE
+            .replace_all(&content, |caps: &regex::Captures| self.pseudonym(&caps[0]))
+            .into_owned();
+        format!("{}{}", &line[..content_start], content)
+    }
+}
+
+/// Write a copy of `path` to `out_path` that is safe to attach to a bug report: every
+/// character that logs in is replaced with `Player1`, `Player2`, ..., the text in
+/// `replacements` is replaced as given, and long numeric ids (GEIDs, account ids)
+/// become stable pseudonyms. Timestamps and line structure are kept.
+#[tauri::command]
+pub fn anonymize_log(
+    path: &str,
+    out_path: &str,
+    replacements: Option<HashMap<String, String>>,
+) -> Result<(), LogError> {
+    let config = crate::player_name_config();
+    let mut redactions: Vec<(String, String)> = replacements.unwrap_or_default().into_iter().collect();
+
+    // Names are collected up front so lines before the login are redacted too
+    let mut reader = open_log_reader(path)?;
+    let mut buf = Vec::new();
+    let mut players = 0;
+    while read_raw_line(&mut reader, &mut buf)? > 0 {
+        if let Some(name) = crate::extract_player_name(&decode_line(&buf), &config) {
+            if !redactions.iter().any(|(from, _)| *from == name) {
+                players += 1;
+                redactions.push((name, format!("Player{}", players)));
+            }
+        }
+    }
+
+    let mut anonymizer = Anonymizer::new(redactions);
+    let mut reader = open_log_reader(path)?;
+    let mut out = BufWriter::new(File::create(out_path)?);
+    while read_raw_line(&mut reader, &mut buf)? > 0 {
+        writeln!(out, "{}", anonymizer.anonymize_line(&decode_line(&buf)))?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_export_events_csv_filters_types() {
+        let path = crate::tests::write_temp_log(
+            "export_csv",
+            "<2024-01-01T12:00:00.000Z> [Notice] <Actor Death> CActor::Kill: 'Victim' [1] in zone 'Stanton, Hurston' killed by 'Killer' [2] using 'weapon' with damage type 'Combat'\n\
+             <2024-01-01T12:00:01.000Z> [Notice] <[STAMINA]> tired\n\
+             plain line\n",
+        );
+        let out = std::env::temp_dir().join(format!("picologs_test_export_{}.csv", std::process::id()));
+
+        let rows = export_events_csv(
+            path.to_str().unwrap(),
+            out.to_str().unwrap(),
+            Some(vec!["actor_death".to_string()]),
+        )
+        .unwrap();
+        let csv = std::fs::read_to_string(&out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows, 1);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("timestamp,event_type,severity,victim,killer"));
+        assert!(lines[1].starts_with("2024-01-01T12:00:00.000Z,actor_death,Notice,Victim,Killer,weapon,Combat,\"Stanton, Hurston\","));
+
+        for file in [path, out] {
+            std::fs::remove_file(file).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_export_session_json_shape() {
+        let path = crate::tests::write_temp_log(
+            "export_json",
+            "Branch: sc-alpha-4.1.0\n\
+             <2024-01-01T12:00:00.000Z> [Notice] <AccountLoginCharacterStatus_Character> Character: ... - name Pilot - ...\n\
+             <2024-01-01T12:10:00.000Z> [Notice] <Actor Death> CActor::Kill: 'Victim' [1] in zone 'zone' killed by 'Pilot' [2] using 'weapon' with damage type 'Combat'\n",
+        );
+        let out = std::env::temp_dir().join(format!("picologs_test_export_{}.json", std::process::id()));
+
+        export_session_json(path.to_str().unwrap(), out.to_str().unwrap()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+
+        assert_eq!(json["schema_version"], SESSION_SCHEMA_VERSION);
+        assert_eq!(json["metadata"]["player_name"], "Pilot");
+        assert_eq!(json["metadata"]["game_version"], "sc-alpha-4.1.0");
+        assert_eq!(json["stats"]["kills"], 1);
+        assert_eq!(json["events"].as_array().unwrap().len(), 2);
+
+        for file in [path, out] {
+            std::fs::remove_file(file).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_anonymize_log() {
+        let path = crate::tests::write_temp_log(
+            "anonymize",
+            "<2024-01-01T11:59:00.000Z> [Notice] early mention of Drift-Runner\n\
+             <2024-01-01T12:00:00.000Z> [Notice] <AccountLoginCharacterStatus_Character> Character: geid 201234567890 - accountId 1234567 - name Drift-Runner - state STATE_CURRENT\n\
+             <2024-01-01T12:10:00.000Z> [Notice] <Actor Death> CActor::Kill: 'Drift-Runner' [201234567890] in zone 'zone' killed by 'Wingman' [209876543210] using 'weapon' [Class unknown] with damage type 'Combat'\n",
+        );
+        let out = std::env::temp_dir().join(format!("picologs_test_anonymized_{}.log", std::process::id()));
+        let replacements = HashMap::from([("Wingman".to_string(), "Friend".to_string())]);
+
+        anonymize_log(path.to_str().unwrap(), out.to_str().unwrap(), Some(replacements)).unwrap();
+        let log = std::fs::read_to_string(&out).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+
+        assert_eq!(lines[0], "<2024-01-01T11:59:00.000Z> [Notice] early mention of Player1");
+        assert!(lines[1].contains("geid 000000000001 - accountId 0000002 - name Player1 - state"));
+        assert!(lines[2].starts_with("<2024-01-01T12:10:00.000Z> [Notice] <Actor Death>"));
+        assert!(lines[2].contains("'Player1' [000000000001] in zone 'zone' killed by 'Friend' [000000000003]"));
+        assert!(!log.contains("Drift-Runner") && !log.contains("201234567890"));
+
+        for file in [path, out] {
+            std::fs::remove_file(file).unwrap();
+        }
+    }
+}
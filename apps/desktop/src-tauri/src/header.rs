@@ -0,0 +1,227 @@
+//! The banner SC writes at the top of Game.log: build branch, versions and the first
+//! timestamped lines
+
+use std::io::BufRead;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::LogError;
+use crate::events::parse_timestamp;
+use crate::{decode_line, open_log_reader, read_raw_line, TIMESTAMP_RE};
+
+/// How much of the start of a log is inspected
+pub(crate) const HEADER_BYTES: usize = 16 * 1024;
+
+lazy_static! {
+    // Branch: sc-alpha-4.1.0, sc-alpha-4.1.0-ptu, sc-alpha-4.1.0-tech-preview
+    static ref BRANCH_RE: Regex = Regex::new(r"\b(sc-[a-z]+-\d+(?:\.\d+)*(?:-[A-Za-z0-9.]+)*)\b").unwrap();
+    // FileVersion: 4.1.0.9488062 (older headers without a branch line)
+    static ref FILE_VERSION_RE: Regex = Regex::new(r"(?:FileVersion|ProductVersion):\s*(\d+(?:\.\d+)+)").unwrap();
+}
+
+/// Result of validate_log_file
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct LogValidation {
+    pub looks_like_sc_log: bool,
+    pub detected_version: Option<String>,
+    pub first_timestamp_ms: Option<i64>,
+}
+
+/// Lines from the first HEADER_BYTES of a log
+fn read_header(path: &str) -> Result<Vec<String>, LogError> {
+    let mut lines = Vec::new();
+    read_header_lines(&mut open_log_reader(path)?, &mut lines)?;
+    Ok(lines)
+}
+
+/// Add the lines from the first HEADER_BYTES of `reader` to `lines` as they are read
+fn read_header_lines<R: BufRead>(reader: &mut R, lines: &mut Vec<String>) -> Result<(), LogError> {
+    let mut buf = Vec::new();
+    let mut bytes = 0;
+
+    while bytes < HEADER_BYTES {
+        let bytes_read = read_raw_line(reader, &mut buf)?;
+        if bytes_read == 0 {
+            break;
+        }
+        bytes += bytes_read;
+        lines.push(decode_line(&buf).into_owned());
+    }
+    Ok(())
+}
+
+/// Build branch from the header (`sc-alpha-4.1.0`), or the file version if no branch is logged
+fn detect_version(header: &[String]) -> Option<String> {
+    let find = |re: &Regex| {
+        header
+            .iter()
+            .find_map(|line| re.captures(line).map(|caps| caps[1].to_string()))
+    };
+    find(&BRANCH_RE).or_else(|| find(&FILE_VERSION_RE))
+}
+
+fn validate_header(header: &[String]) -> LogValidation {
+    let first_timestamp_ms = header
+        .iter()
+        .filter(|line| TIMESTAMP_RE.is_match(line))
+        .find_map(|line| parse_timestamp(line));
+    let detected_version = detect_version(header);
+
+    // SC's `<2024-01-01T12:00:00.000Z>` prefix plus something only SC writes
+    let sc_banner = header
+        .iter()
+        .any(|line| line.contains("Log started") || line.contains("StarCitizen") || line.contains("Star Citizen"));
+    let looks_like_sc_log = first_timestamp_ms.is_some() && (detected_version.is_some() || sc_banner);
+
+    LogValidation {
+        looks_like_sc_log,
+        detected_version,
+        first_timestamp_ms,
+    }
+}
+
+/// Release channel a build was published to
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum ReleaseChannel {
+    #[serde(rename = "LIVE")]
+    Live,
+    #[serde(rename = "PTU")]
+    Ptu,
+    #[serde(rename = "EPTU")]
+    Eptu,
+    #[serde(rename = "TECH-PREVIEW")]
+    TechPreview,
+}
+
+/// Channel named by the suffix of a build branch (`sc-alpha-4.1.0-ptu`); a branch
+/// without one is LIVE. None for a bare file version, which doesn't name a channel.
+pub fn release_channel(version: &str) -> Option<ReleaseChannel> {
+    let version = version.to_ascii_lowercase();
+    if !version.starts_with("sc-") {
+        return None;
+    }
+    Some(if version.contains("tech-preview") || version.contains("techpreview") {
+        ReleaseChannel::TechPreview
+    } else if version.contains("eptu") {
+        ReleaseChannel::Eptu
+    } else if version.contains("ptu") {
+        ReleaseChannel::Ptu
+    } else {
+        ReleaseChannel::Live
+    })
+}
+
+/// Build branch or version the log was written by, e.g. `sc-alpha-4.1.0`.
+/// None if the header doesn't carry one (truncated or very old logs).
+#[tauri::command]
+pub fn read_game_version(path: &str) -> Result<Option<String>, LogError> {
+    Ok(detect_version(&read_header(path)?))
+}
+
+/// read_game_version on the start of a log already read, decoded by `reader`. The
+/// bytes may stop mid-stream (a gzip archive cut off), so a read error just ends the
+/// header.
+pub fn game_version_from(mut reader: impl BufRead) -> Option<String> {
+    let mut lines = Vec::new();
+    let _ = read_header_lines(&mut reader, &mut lines);
+    detect_version(&lines)
+}
+
+/// Check the start of a file for SC's timestamp format and build banner, so the
+/// picker can warn before watching a file that isn't a Game.log
+#[tauri::command]
+pub fn validate_log_file(path: &str) -> Result<LogValidation, LogError> {
+    Ok(validate_header(&read_header(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_validate_sc_header() {
+        let header = lines(
+            "Log started on Mon Jan  1 12:00:00 2024\n\
+             Executable: StarCitizen.exe\n\
+             Branch: sc-alpha-4.1.0\n\
+             FileVersion: 4.1.0.9488062\n\
+             <2024-01-01T12:00:01.000Z> [Notice] <Init> starting",
+        );
+        let validation = validate_header(&header);
+
+        assert!(validation.looks_like_sc_log);
+        assert_eq!(validation.detected_version.as_deref(), Some("sc-alpha-4.1.0"));
+        assert_eq!(validation.first_timestamp_ms, Some(1_704_110_401_000));
+    }
+
+    #[test]
+    fn test_validate_rejects_other_text() {
+        let validation = validate_header(&lines("just some notes\n2024-01-01 12:00 meeting"));
+        assert_eq!(
+            validation,
+            LogValidation {
+                looks_like_sc_log: false,
+                detected_version: None,
+                first_timestamp_ms: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_game_version() {
+        let path = crate::tests::write_temp_log(
+            "game_version",
+            "Log started on Mon Jan  1 12:00:00 2024\nBranch: sc-alpha-4.1.0\n<2024-01-01T12:00:01.000Z> [Notice] <Init>\n",
+        );
+        assert_eq!(read_game_version(path.to_str().unwrap()).unwrap().as_deref(), Some("sc-alpha-4.1.0"));
+
+        let bare = crate::tests::write_temp_log("no_game_version", "<2024-01-01T12:00:01.000Z> [Notice] <Init>\n");
+        assert_eq!(read_game_version(bare.to_str().unwrap()).unwrap(), None);
+
+        for file in [path, bare] {
+            std::fs::remove_file(file).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_game_version_from_cut_off_gzip() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut log = "Log started on Mon Jan  1 12:00:00 2024\nBranch: sc-alpha-4.1.0-ptu\n".to_string();
+        for i in 0..2000 {
+            log.push_str(&format!("<2024-01-01T12:00:01.000Z> [Notice] <Init> step {}\n", i));
+        }
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(log.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let cut_off = &compressed[..compressed.len() / 2];
+        let reader = std::io::BufReader::new(flate2::read::MultiGzDecoder::new(cut_off));
+        assert_eq!(game_version_from(reader).as_deref(), Some("sc-alpha-4.1.0-ptu"));
+    }
+
+    #[test]
+    fn test_release_channel() {
+        let channel = |branch: &str| {
+            let version = detect_version(&lines(&format!("Branch: {}\n", branch)))?;
+            release_channel(&version)
+        };
+        assert_eq!(channel("sc-alpha-4.1.0"), Some(ReleaseChannel::Live));
+        assert_eq!(channel("sc-alpha-4.1.0-ptu"), Some(ReleaseChannel::Ptu));
+        assert_eq!(channel("sc-alpha-4.1.0-EPTU"), Some(ReleaseChannel::Eptu));
+        assert_eq!(channel("sc-alpha-4.1.0-tech-preview"), Some(ReleaseChannel::TechPreview));
+        assert_eq!(release_channel("4.1.0.9488062"), None);
+    }
+
+    #[test]
+    fn test_detect_version_falls_back_to_file_version() {
+        let header = lines("Log started\nFileVersion: 3.24.2.9381373\n");
+        assert_eq!(detect_version(&header).as_deref(), Some("3.24.2.9381373"));
+    }
+}
@@ -0,0 +1,216 @@
+//! Pattern signatures remembered across restarts
+//!
+//! Pattern discovery reports each signature once per log. The signatures and the
+//! patterns they came from are persisted in their own store file, so the known schema
+//! of a log keeps growing over sessions and reopening it doesn't report everything as
+//! new again. Each log keeps at most MAX_KNOWN_PATTERNS, and at most
+//! MAX_KNOWN_PATTERN_LOGS logs are kept; past either the least recently seen go first.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use tauri_plugin_store::StoreExt;
+
+use crate::error::LogError;
+use crate::watcher::now_ms;
+use crate::RawLogPattern;
+
+/// Store file the known patterns are persisted in
+const PATTERN_STORE: &str = "known-patterns.json";
+
+/// Key of the known patterns within PATTERN_STORE
+const KNOWN_PATTERNS_KEY: &str = "knownPatterns";
+
+/// Patterns kept per log
+const MAX_KNOWN_PATTERNS: usize = 5_000;
+
+/// Logs whose patterns are kept
+const MAX_KNOWN_PATTERN_LOGS: usize = 20;
+
+/// A reported pattern and when a read last came across its signature
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+struct KnownPattern {
+    pattern: RawLogPattern,
+    /// Unix epoch milliseconds
+    last_seen_ms: i64,
+}
+
+/// Patterns reported for one log, keyed by signature
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+struct KnownLog {
+    patterns: HashMap<String, KnownPattern>,
+    /// Unix epoch milliseconds of the last read that reported patterns
+    last_seen_ms: i64,
+}
+
+impl KnownLog {
+    /// Drop the least recently seen patterns beyond `limit`
+    fn evict(&mut self, limit: usize) {
+        if self.patterns.len() <= limit {
+            return;
+        }
+        let mut by_age: Vec<(i64, String)> = self
+            .patterns
+            .iter()
+            .map(|(signature, known)| (known.last_seen_ms, signature.clone()))
+            .collect();
+        by_age.sort();
+        for (_, signature) in by_age.into_iter().take(self.patterns.len() - limit) {
+            self.patterns.remove(&signature);
+        }
+    }
+}
+
+// Known patterns of each log, keyed by path (cleared by clear_pattern_cache)
+lazy_static! {
+    static ref KNOWN_PATTERNS: Mutex<HashMap<String, KnownLog>> = Mutex::new(HashMap::new());
+}
+
+/// Drop the least recently seen logs beyond `limit`
+fn evict_logs(logs: &mut HashMap<String, KnownLog>, limit: usize) {
+    while logs.len() > limit {
+        let Some(oldest) = logs
+            .iter()
+            .min_by_key(|(_, log)| log.last_seen_ms)
+            .map(|(path, _)| path.clone())
+        else {
+            return;
+        };
+        logs.remove(&oldest);
+    }
+}
+
+/// Keep only the patterns never reported for `path` before and remember them. Known
+/// ones count as seen again, so patterns that still occur aren't evicted.
+pub fn drop_known(path: &str, patterns: &mut Vec<RawLogPattern>) {
+    let now = now_ms();
+    let mut logs = KNOWN_PATTERNS.lock().unwrap();
+    let log = logs.entry(path.to_string()).or_default();
+    log.last_seen_ms = now;
+
+    patterns.retain(|pattern| match log.patterns.get_mut(&pattern.signature) {
+        Some(known) => {
+            known.last_seen_ms = now;
+            false
+        }
+        None => {
+            log.patterns.insert(
+                pattern.signature.clone(),
+                KnownPattern {
+                    pattern: pattern.clone(),
+                    last_seen_ms: now,
+                },
+            );
+            true
+        }
+    });
+
+    log.evict(MAX_KNOWN_PATTERNS);
+    evict_logs(&mut logs, MAX_KNOWN_PATTERN_LOGS);
+}
+
+/// Forget the patterns reported for `path`
+pub fn forget(path: &str) {
+    // Dropped after the lock is released
+    let removed = KNOWN_PATTERNS.lock().unwrap().remove(path);
+    drop(removed);
+}
+
+/// Forget the patterns reported for every log
+pub fn forget_all() {
+    let cleared = std::mem::take(&mut *KNOWN_PATTERNS.lock().unwrap());
+    drop(cleared);
+}
+
+/// Write the known patterns to PATTERN_STORE
+pub fn save_known_patterns(app: &tauri::AppHandle) -> Result<(), LogError> {
+    let value = serde_json::to_value(&*KNOWN_PATTERNS.lock().unwrap()).map_err(|e| LogError::Io(e.to_string()))?;
+    let store = app.store(PATTERN_STORE).map_err(|e| LogError::Io(e.to_string()))?;
+    store.set(KNOWN_PATTERNS_KEY, value);
+    store.save().map_err(|e| LogError::Io(e.to_string()))
+}
+
+/// Restore the known patterns saved by save_known_patterns
+pub fn load_known_patterns(app: &tauri::AppHandle) {
+    let Ok(store) = app.store(PATTERN_STORE) else {
+        return;
+    };
+    if let Some(value) = store.get(KNOWN_PATTERNS_KEY) {
+        match serde_json::from_value::<HashMap<String, KnownLog>>(value) {
+            Ok(logs) => *KNOWN_PATTERNS.lock().unwrap() = logs,
+            Err(e) => eprintln!("[Rust] Ignoring invalid stored known patterns: {}", e),
+        }
+    }
+}
+
+/// Every pattern reported for a log so far, including in earlier sessions, in the
+/// order they were first observed
+#[tauri::command]
+pub fn get_known_patterns(path: &str) -> Vec<RawLogPattern> {
+    let logs = KNOWN_PATTERNS.lock().unwrap();
+    let mut patterns: Vec<RawLogPattern> = logs
+        .get(path)
+        .map(|log| log.patterns.values().map(|known| known.pattern.clone()).collect())
+        .unwrap_or_default();
+    patterns.sort_by(|a, b| {
+        a.first_seen_ms
+            .cmp(&b.first_seen_ms)
+            .then_with(|| a.signature.cmp(&b.signature))
+    });
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(signature: &str) -> RawLogPattern {
+        RawLogPattern {
+            event_name: None,
+            severity: None,
+            teams: Vec::new(),
+            subsystems: Vec::new(),
+            signature: signature.to_string(),
+            example_line: signature.to_string(),
+            first_seen_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_drop_known_reports_each_signature_once() {
+        let path = "known_patterns_test.log";
+        let mut first = vec![pattern("a"), pattern("b")];
+        drop_known(path, &mut first);
+        let mut second = vec![pattern("b"), pattern("c")];
+        drop_known(path, &mut second);
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].signature, "c");
+        let known: Vec<String> = get_known_patterns(path).into_iter().map(|p| p.signature).collect();
+        assert_eq!(known, vec!["a", "b", "c"]);
+
+        forget(path);
+        assert!(get_known_patterns(path).is_empty());
+    }
+
+    #[test]
+    fn test_evict_drops_least_recently_seen() {
+        let mut log = KnownLog::default();
+        for (signature, last_seen_ms) in [("old", 1), ("new", 3), ("middle", 2)] {
+            log.patterns.insert(
+                signature.to_string(),
+                KnownPattern {
+                    pattern: pattern(signature),
+                    last_seen_ms,
+                },
+            );
+        }
+
+        log.evict(2);
+        let mut kept: Vec<&str> = log.patterns.keys().map(String::as_str).collect();
+        kept.sort();
+        assert_eq!(kept, vec!["middle", "new"]);
+    }
+}
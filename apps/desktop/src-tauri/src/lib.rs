@@ -1,11 +1,38 @@
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tauri::{Emitter, Manager};
+use flate2::read::MultiGzDecoder;
 use lazy_static::lazy_static;
 use regex::Regex;
+use error::LogError;
 // use tauri_plugin_window_state::{StateFlags, Builder as WindowStateBuilder};
 
+mod cursors;
+mod diff;
+mod discovery;
+mod error;
+mod events;
+mod export;
+mod header;
+mod known_patterns;
+mod line_index;
+mod markers;
+mod parsers;
+mod profiles;
+mod remote;
+mod search;
+mod stats;
+mod timeline;
+mod travel;
+mod upload;
+mod watcher;
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -16,20 +43,51 @@ fn greet(name: &str) -> String {
 #[derive(serde::Serialize)]
 pub struct LogMetadata {
     line_count: usize,
+    /// Lines ending in a newline; resume from this count (a trailing partial line is
+    /// counted in `line_count` only)
+    committed_line_count: usize,
     player_name: Option<String>,
+    /// Last modification time as Unix epoch milliseconds (None if the filesystem doesn't record it)
+    modified_ms: Option<i64>,
+    size_bytes: u64,
+    /// Build branch from the log header, e.g. `sc-alpha-4.1.0`
+    game_version: Option<String>,
+    /// Channel of `game_version`, so the UI can warn when e.g. a PTU log is watched
+    /// while the player expects LIVE
+    channel: Option<header::ReleaseChannel>,
 }
 
 /// Response from read_log_update command - single-pass file reading
 #[derive(serde::Serialize)]
 pub struct LogUpdate {
     line_count: usize,
+    /// Lines ending in a newline. A trailing partial line (SC mid-write) is counted in
+    /// `line_count` but left out of `new_lines`; resume from this count to re-read it whole.
+    committed_line_count: usize,
     player_name: Option<String>,
     new_lines: Vec<String>,
+    /// Line number of each `new_lines` entry, for "show in context" links and stable keys.
+    /// Absolute for read_log_update; counted from `from_byte` for read_log_update_at.
+    line_numbers: Vec<usize>,
+    /// Only patterns never reported before for this file (until clear_pattern_cache),
+    /// so the discovery feed can append them as-is
     patterns: Vec<RawLogPattern>,
+    /// Byte offset just past the last line read (resume point for read_log_update_at).
+    /// Byte offsets are u64 everywhere, never usize, so logs past 4GB resume correctly
+    /// on 32-bit builds too. Line numbers stay usize.
+    end_offset: u64,
+    /// File is shorter than the requested position - caller should restart from zero
+    rotated: bool,
+    /// Read was aborted via cancel_read - the result covers only the lines read so far
+    cancelled: bool,
+    /// With `extract_patterns`: marker-matched lines a structured parser rejected
+    unparsed: Vec<events::UnparsedEvent>,
+    /// Line numbers of `new_lines` entries cut to MAX_LINE_BYTES (they end in TRUNCATION_MARKER)
+    truncated_lines: Vec<usize>,
 }
 
 /// Raw log pattern extracted from a log line for schema discovery
-#[derive(serde::Serialize, Clone)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RawLogPattern {
     pub event_name: Option<String>,
@@ -38,20 +96,41 @@ pub struct RawLogPattern {
     pub subsystems: Vec<String>,
     pub signature: String,
     pub example_line: String,
+    /// Timestamp of the line the pattern was first observed on, as Unix epoch milliseconds
+    pub first_seen_ms: Option<i64>,
 }
 
 // Regex patterns for log pattern extraction (compiled once, reused)
 lazy_static! {
-    static ref TIMESTAMP_RE: Regex = Regex::new(r"^<\d{4}-\d{2}-\d{2}T[\d:.]+Z>\s*").unwrap();
+    static ref TIMESTAMP_RE: Regex = Regex::new(r"^<\d{4}-\d{2}-\d{2}T[\d:.]+(?:Z|[+-]\d{2}:\d{2})>\s*").unwrap();
     static ref SEVERITY_RE: Regex = Regex::new(r"\[(Notice|Error|Trace|Warning)\]").unwrap();
     static ref EVENT_NAME_RE: Regex = Regex::new(r"<([A-Za-z_:][A-Za-z0-9_:]*(?:::[A-Za-z0-9_<>]+)*)>").unwrap();
     static ref TEAM_TAG_RE: Regex = Regex::new(r"\[Team_([A-Za-z]+)\]").unwrap();
     static ref SUBSYSTEM_TAG_RE: Regex = Regex::new(r"\[([A-Za-z][A-Za-z0-9_]*)\]").unwrap();
+    // Instance ids and GEIDs embedded in event names (`Entity_12345`, `Obj_0x1f3a`)
+    static ref EVENT_NAME_ID_RE: Regex = Regex::new(r"0[xX][0-9a-fA-F]+|\d+").unwrap();
+}
+
+// First bytes of each file as of the previous read, keyed by path (rotation detection)
+lazy_static! {
+    static ref LOG_HEADS: Mutex<HashMap<String, Vec<u8>>> = Mutex::new(HashMap::new());
 }
 
+/// Number of leading bytes compared to detect that a log was replaced.
+/// SC writes a "Log started" line with the session timestamp first, so a restart
+/// changes the head even when the new file has already grown past the old offset.
+const LOG_HEAD_BYTES: usize = 256;
+
 const SEVERITY_TAGS: &[&str] = &["Notice", "Error", "Trace", "Warning"];
 
-/// Generate a stable signature for pattern deduplication
+/// Event name with embedded ids replaced by `#`, so every instance of an event
+/// shares one signature
+fn normalize_event_name(name: &str) -> Cow<'_, str> {
+    EVENT_NAME_ID_RE.replace_all(name, "#")
+}
+
+/// Generate a stable signature for pattern deduplication.
+/// Ids in the event name are normalized away (see normalize_event_name).
 fn generate_signature(
     event_name: &Option<String>,
     severity: &Option<String>,
@@ -65,25 +144,25 @@ fn generate_signature(
 
     format!(
         "{}|{}|{}|{}",
-        event_name.as_deref().unwrap_or("null"),
+        event_name.as_deref().map_or(Cow::Borrowed("null"), normalize_event_name),
         severity.as_deref().unwrap_or("null"),
         sorted_teams.join(","),
         sorted_subsystems.join(",")
     )
 }
 
-/// Extract pattern metadata from a log line
-fn extract_log_pattern(line: &str) -> Option<RawLogPattern> {
+/// Extract pattern metadata from a log line in the format of `profile`
+fn extract_log_pattern(line: &str, profile: &profiles::LogProfile) -> Option<RawLogPattern> {
     // Skip lines that don't start with timestamp
-    if !line.starts_with('<') || !TIMESTAMP_RE.is_match(line) {
+    if !profile.timestamp_re.is_match(line) {
         return None;
     }
 
     // Remove timestamp for parsing
-    let content = TIMESTAMP_RE.replace(line, "");
+    let content = profile.timestamp_re.replace(line, "");
 
     // Extract severity
-    let severity = SEVERITY_RE.captures(&content)
+    let severity = profile.severity_re.captures(&content)
         .map(|c| c.get(1).unwrap().as_str().to_string());
 
     // Extract event name (skip timestamp-like patterns)
@@ -122,6 +201,7 @@ fn extract_log_pattern(line: &str) -> Option<RawLogPattern> {
         subsystems,
         signature,
         example_line: line.to_string(),
+        first_seen_ms: profile.parse_timestamp(line),
     })
 }
 
@@ -186,180 +266,1690 @@ const EVENT_MARKERS: &[&str] = &[
     "<Failed to get starmap route data!>",    // Starmap error
 ];
 
-/// Check if a line contains any event marker
+// Marker list set from the frontend via set_event_markers (empty = use EVENT_MARKERS)
+lazy_static! {
+    static ref CUSTOM_EVENT_MARKERS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+}
+
+// Markers muted via set_disabled_markers: lines containing one are not reported
+lazy_static! {
+    static ref DISABLED_MARKERS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+}
+
+/// Store file and key the custom marker list is persisted under
+const SETTINGS_STORE: &str = "settings.json";
+const EVENT_MARKERS_KEY: &str = "eventMarkers";
+const DISABLED_MARKERS_KEY: &str = "disabledMarkers";
+
+/// Check a line against the custom marker list, or the compiled defaults if it is empty
+fn matches_event_marker(line: &str, custom_markers: &[String]) -> bool {
+    if custom_markers.is_empty() {
+        EVENT_MARKERS.iter().any(|marker| line.contains(marker))
+    } else {
+        custom_markers.iter().any(|marker| line.contains(marker.as_str()))
+    }
+}
+
+/// Whether a line contains one of the muted markers
+fn is_marker_disabled(line: &str, disabled_markers: &[String]) -> bool {
+    disabled_markers.iter().any(|marker| line.contains(marker.as_str()))
+}
+
+/// Check if a line contains any event marker (literal markers first, then marker rules).
+/// Muted markers are only checked after a match, so unmatched lines cost nothing extra.
 fn contains_event_marker(line: &str) -> bool {
-    EVENT_MARKERS.iter().any(|marker| line.contains(marker))
+    (matches_event_marker(line, &CUSTOM_EVENT_MARKERS.lock().unwrap()) || markers::matches_rule(line))
+        && !is_marker_disabled(line, &DISABLED_MARKERS.lock().unwrap())
 }
 
-/// Read log file in a single pass - returns line count, player name (optional), filtered new lines, and patterns
-/// Only returns lines that contain event markers (95% reduction in data sent to JavaScript)
+/// Replace the active event markers at runtime (e.g. after a patch renamed an event)
+/// An empty list restores the compiled EVENT_MARKERS. Persisted across restarts.
 #[tauri::command]
-fn read_log_update(
-    path: &str,
+fn set_event_markers(markers: Vec<String>, app: tauri::AppHandle) -> Result<(), String> {
+    use tauri_plugin_store::StoreExt;
+
+    // An empty marker would match every line
+    let markers: Vec<String> = markers.into_iter().filter(|m| !m.is_empty()).collect();
+
+    let store = app.store(SETTINGS_STORE).map_err(|e| e.to_string())?;
+    store.set(EVENT_MARKERS_KEY, serde_json::json!(markers));
+    store.save().map_err(|e| e.to_string())?;
+
+    *CUSTOM_EVENT_MARKERS.lock().unwrap() = markers;
+    Ok(())
+}
+
+/// Mute markers (e.g. a noisy `<[STAMINA]`) without replacing the marker list.
+/// An empty list unmutes everything. Persisted across restarts.
+#[tauri::command]
+fn set_disabled_markers(markers: Vec<String>, app: tauri::AppHandle) -> Result<(), String> {
+    use tauri_plugin_store::StoreExt;
+
+    // An empty marker would mute every line
+    let markers: Vec<String> = markers.into_iter().filter(|m| !m.is_empty()).collect();
+
+    let store = app.store(SETTINGS_STORE).map_err(|e| e.to_string())?;
+    store.set(DISABLED_MARKERS_KEY, serde_json::json!(markers));
+    store.save().map_err(|e| e.to_string())?;
+
+    *DISABLED_MARKERS.lock().unwrap() = markers;
+    Ok(())
+}
+
+/// Restore the custom marker list persisted by set_event_markers and the muted
+/// markers persisted by set_disabled_markers
+fn load_event_markers(app: &tauri::AppHandle) {
+    use tauri_plugin_store::StoreExt;
+
+    let Ok(store) = app.store(SETTINGS_STORE) else {
+        return;
+    };
+    if let Some(value) = store.get(EVENT_MARKERS_KEY) {
+        match serde_json::from_value::<Vec<String>>(value) {
+            Ok(markers) => *CUSTOM_EVENT_MARKERS.lock().unwrap() = markers,
+            Err(e) => eprintln!("[Rust] Ignoring invalid stored event markers: {}", e),
+        }
+    }
+    if let Some(value) = store.get(DISABLED_MARKERS_KEY) {
+        match serde_json::from_value::<Vec<String>>(value) {
+            Ok(markers) => *DISABLED_MARKERS.lock().unwrap() = markers,
+            Err(e) => eprintln!("[Rust] Ignoring invalid stored disabled markers: {}", e),
+        }
+    }
+}
+
+/// Where the character name sits in a login line:
+/// `<marker> ... <prefix>Name<delimiter> ...`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct PlayerNameConfig {
+    pub marker: String,
+    pub prefix: String,
+    pub delimiter: String,
+}
+
+impl Default for PlayerNameConfig {
+    fn default() -> Self {
+        Self {
+            marker: "AccountLoginCharacterStatus_Character".to_string(),
+            prefix: "name ".to_string(),
+            delimiter: " - ".to_string(),
+        }
+    }
+}
+
+impl PlayerNameConfig {
+    /// An empty marker or delimiter would match every line or yield empty names
+    pub fn validate(&self) -> Result<(), String> {
+        if self.marker.is_empty() || self.delimiter.is_empty() {
+            return Err("Player name marker and delimiter must not be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+// Login line format set from the frontend via set_player_name_config
+lazy_static! {
+    static ref PLAYER_NAME_CONFIG: Mutex<PlayerNameConfig> = Mutex::new(PlayerNameConfig::default());
+}
+
+/// Store key the login line format is persisted under
+const PLAYER_NAME_CONFIG_KEY: &str = "playerNameConfig";
+
+/// Snapshot of the active login line format (taken once per read, not per line)
+fn player_name_config() -> PlayerNameConfig {
+    PLAYER_NAME_CONFIG.lock().unwrap().clone()
+}
+
+/// Extract the character name from a login line.
+/// Reads up to the delimiter (not the next space) so hyphenated names and names
+/// containing spaces come through whole.
+fn extract_player_name(line: &str, config: &PlayerNameConfig) -> Option<String> {
+    if !line.contains(config.marker.as_str()) {
+        return None;
+    }
+    let name_start = line.find(config.prefix.as_str())? + config.prefix.len();
+    let end = line[name_start..].find(config.delimiter.as_str())?;
+    Some(line[name_start..name_start + end].to_string())
+}
+
+/// Replace the login line format used to find the player name (e.g. after CIG
+/// changes it). Persisted across restarts.
+#[tauri::command]
+fn set_player_name_config(config: PlayerNameConfig, app: tauri::AppHandle) -> Result<(), String> {
+    use tauri_plugin_store::StoreExt;
+
+    config.validate()?;
+
+    let store = app.store(SETTINGS_STORE).map_err(|e| e.to_string())?;
+    store.set(PLAYER_NAME_CONFIG_KEY, serde_json::json!(config));
+    store.save().map_err(|e| e.to_string())?;
+
+    *PLAYER_NAME_CONFIG.lock().unwrap() = config;
+    Ok(())
+}
+
+/// Restore the login line format persisted by set_player_name_config
+fn load_player_name_config(app: &tauri::AppHandle) {
+    use tauri_plugin_store::StoreExt;
+
+    let Ok(store) = app.store(SETTINGS_STORE) else {
+        return;
+    };
+    if let Some(value) = store.get(PLAYER_NAME_CONFIG_KEY) {
+        match serde_json::from_value::<PlayerNameConfig>(value) {
+            Ok(config) => *PLAYER_NAME_CONFIG.lock().unwrap() = config,
+            Err(e) => eprintln!("[Rust] Ignoring invalid stored player name config: {}", e),
+        }
+    }
+}
+
+/// Read a single line into `buf` (trailing `\n` / `\r\n` stripped)
+/// Returns the number of bytes consumed from the reader, 0 at end of file
+fn read_raw_line<R: BufRead>(reader: &mut R, buf: &mut Vec<u8>) -> Result<usize, LogError> {
+    read_raw_line_retrying(reader, buf, &TRANSIENT_RETRY_DELAYS)
+}
+
+/// Waits between attempts when a read or open fails transiently
+const TRANSIENT_RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(200),
+];
+
+/// Whether an I/O error can clear up by itself, e.g. SC holding a Windows sharing or
+/// lock violation on the log for a moment while it writes during combat
+fn is_transient(error: &std::io::Error) -> bool {
+    use std::io::ErrorKind;
+    if matches!(
+        error.kind(),
+        ErrorKind::Interrupted | ErrorKind::WouldBlock | ErrorKind::ResourceBusy
+    ) {
+        return true;
+    }
+    // ERROR_SHARING_VIOLATION, ERROR_LOCK_VIOLATION
+    cfg!(windows) && matches!(error.raw_os_error(), Some(32 | 33))
+}
+
+/// Run `op`, retrying after each of `delays` while it fails transiently
+fn retry_transient<T>(delays: &[Duration], mut op: impl FnMut() -> std::io::Result<T>) -> std::io::Result<T> {
+    let mut delays = delays.iter();
+    loop {
+        match op() {
+            Err(e) if is_transient(&e) => match delays.next() {
+                Some(delay) => std::thread::sleep(*delay),
+                None => return Err(e),
+            },
+            result => return result,
+        }
+    }
+}
+
+/// Call `visit` with the line number and text of every line from `from_line` onward.
+/// A final line without a newline is still being written and is left out, as in
+/// LogScanner::read_from, so it is read whole on the next call.
+fn for_each_line(path: &str, from_line: usize, mut visit: impl FnMut(usize, &str)) -> Result<(), LogError> {
+    let mut reader = open_log_reader(path)?;
+    let mut buf = Vec::new();
+    let mut line_number = 0usize;
+
+    loop {
+        let bytes_read = read_raw_line(&mut reader, &mut buf)?;
+        // No newline was stripped: end of file or a partial line
+        if bytes_read == buf.len() {
+            break;
+        }
+        if line_number >= from_line {
+            visit(line_number, &decode_line(&buf));
+        }
+        line_number += 1;
+    }
+    Ok(())
+}
+
+/// read_raw_line with explicit retry delays. Bytes read before a failed attempt are
+/// kept in `buf`, so a retry continues the same line.
+fn read_raw_line_retrying<R: BufRead>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    delays: &[Duration],
+) -> Result<usize, LogError> {
+    buf.clear();
+    retry_transient(delays, || reader.read_until(b'\n', buf))?;
+    let bytes_read = buf.len();
+
+    if buf.last() == Some(&b'\n') {
+        buf.pop();
+        if buf.last() == Some(&b'\r') {
+            buf.pop();
+        }
+    }
+
+    Ok(bytes_read)
+}
+
+/// Byte order mark some editors and export tools put at the start of a file
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Longest line kept whole in results. SC occasionally logs serialized blobs of
+/// several MB on one line, which would otherwise be copied into `new_lines` as-is.
+const MAX_LINE_BYTES: usize = 64 * 1024;
+
+/// Appended to a line cut to MAX_LINE_BYTES
+const TRUNCATION_MARKER: &str = "…[truncated]";
+
+/// Cut a line to at most `max_bytes` (on a char boundary) plus TRUNCATION_MARKER.
+/// Returns `Cow::Owned` only when the line was cut.
+fn truncate_line(line: &str, max_bytes: usize) -> Cow<'_, str> {
+    if line.len() <= max_bytes {
+        return Cow::Borrowed(line);
+    }
+    let mut end = max_bytes;
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    Cow::Owned(format!("{}{}", &line[..end], TRUNCATION_MARKER))
+}
+
+/// Decode a raw line read by read_raw_line, replacing invalid UTF-8 with U+FFFD
+/// so a single garbage byte (SC writes some during crashes) can't abort a whole scan.
+/// A leading BOM is dropped so the first line still starts with its `<` timestamp.
+/// Returns `Cow::Owned` only when bytes had to be replaced.
+fn decode_line(buf: &[u8]) -> Cow<'_, str> {
+    String::from_utf8_lossy(buf.strip_prefix(UTF8_BOM).unwrap_or(buf))
+}
+
+// Tokens passed to cancel_read whose reads have not yet noticed
+lazy_static! {
+    static ref CANCELLED_READS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+/// Lines between checks of the cancelled-token set
+const CANCEL_CHECK_INTERVAL: usize = 4096;
+
+/// Telemetry of one scan, kept in release builds so perf reports can include it
+#[derive(serde::Serialize, Clone, Debug, Default, PartialEq)]
+pub struct ReadStats {
+    /// Lines at or after `from_line`
+    pub lines_scanned: usize,
+    /// Lines that matched an event marker
+    pub matched: usize,
+    /// Patterns not reported by an earlier read
+    pub unique_patterns: usize,
+    pub duration_ms: u64,
+}
+
+// Stats of the most recently finished scan (all zeros before the first one)
+lazy_static! {
+    static ref LAST_READ_STATS: Mutex<ReadStats> = Mutex::new(ReadStats::default());
+}
+
+/// Stats of the most recent read, in every build, for attaching to perf bug reports
+#[tauri::command]
+fn get_last_read_stats() -> ReadStats {
+    LAST_READ_STATS.lock().unwrap().clone()
+}
+
+/// Optional cancellation token of an in-flight read.
+/// The token is forgotten once the read finishes, so tokens should be unique per read.
+struct ReadCancellation {
+    token: Option<String>,
+}
+
+impl ReadCancellation {
+    fn new(token: Option<String>) -> Self {
+        Self { token }
+    }
+
+    /// Whether the read should stop, checked every CANCEL_CHECK_INTERVAL lines
+    fn should_stop(&self, lines_read: usize) -> bool {
+        lines_read.is_multiple_of(CANCEL_CHECK_INTERVAL) && self.is_cancelled()
+    }
+
+    /// Whether cancel_read was called with this read's token
+    fn is_cancelled(&self) -> bool {
+        self.token
+            .as_ref()
+            .is_some_and(|token| CANCELLED_READS.lock().unwrap().contains(token))
+    }
+}
+
+impl Drop for ReadCancellation {
+    fn drop(&mut self) {
+        if let Some(token) = &self.token {
+            CANCELLED_READS.lock().unwrap().remove(token);
+        }
+    }
+}
+
+/// Abort the in-flight read started with `token`.
+/// The read returns early with whatever it had collected, flagged `cancelled`.
+#[tauri::command]
+fn cancel_read(token: String) {
+    CANCELLED_READS.lock().unwrap().insert(token);
+}
+
+/// Read the first LOG_HEAD_BYTES of a file, leaving the file position at the start
+fn read_log_head(file: &mut File) -> Result<Vec<u8>, LogError> {
+    read_file_start(file, LOG_HEAD_BYTES)
+}
+
+/// Read the first `limit` bytes of a file, leaving the file position at the start
+fn read_file_start(file: &mut File, limit: usize) -> Result<Vec<u8>, LogError> {
+    let mut start = Vec::with_capacity(limit);
+    file.by_ref().take(limit as u64).read_to_end(&mut start)?;
+    file.seek(SeekFrom::Start(0))?;
+    Ok(start)
+}
+
+/// Leading bytes of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Whether a log is a gzip archive, by `.gz` extension or the gzip magic bytes
+fn is_gzip(path: &str, head: &[u8]) -> bool {
+    let has_gz_extension = Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"));
+    has_gz_extension || head.starts_with(&GZIP_MAGIC)
+}
+
+/// UTF-16 variant a plain-text log is written in, if any: by its byte order mark, or
+/// for BOM-less files by the zero high bytes of mostly-ASCII text in the head.
+/// SC writes UTF-8; UTF-16 comes from archived logs re-saved in some editors.
+fn utf16_encoding(head: &[u8]) -> Option<&'static encoding_rs::Encoding> {
+    if let Some((encoding, _)) = encoding_rs::Encoding::for_bom(head) {
+        return (encoding != encoding_rs::UTF_8).then_some(encoding);
+    }
+    let pairs = head.len() / 2;
+    if pairs < 8 {
+        return None;
+    }
+    let zeros_at = |parity: usize| head.iter().skip(parity).step_by(2).take(pairs).filter(|&&b| b == 0).count();
+    // Nearly every other byte zero, and (almost) never the ones in between
+    let mostly = |count: usize| count * 10 >= pairs * 9;
+    let rarely = |count: usize| count * 10 <= pairs;
+    match (zeros_at(0), zeros_at(1)) {
+        (even, odd) if mostly(odd) && rarely(even) => Some(encoding_rs::UTF_16LE),
+        (even, odd) if mostly(even) && rarely(odd) => Some(encoding_rs::UTF_16BE),
+        _ => None,
+    }
+}
+
+/// Whether offsets into the text differ from offsets into the file (gzip archives,
+/// UTF-16 logs). Such logs are decoded from the start instead of seeked into.
+fn needs_decoding(path: &str, head: &[u8]) -> bool {
+    is_gzip(path, head) || utf16_encoding(head).is_some()
+}
+
+/// Input read per decode step by Utf16Reader
+const TRANSCODE_CHUNK_BYTES: usize = 8 * 1024;
+
+/// Converts a UTF-16 stream to UTF-8 as it is read, so the line reader and parsers
+/// only ever see UTF-8. Invalid sequences become U+FFFD.
+struct Utf16Reader<R> {
+    inner: R,
+    decoder: encoding_rs::Decoder,
+    input: Vec<u8>,
+    /// Decoded bytes not yet handed out, from `output_pos` on
+    output: Vec<u8>,
+    output_pos: usize,
+    finished: bool,
+}
+
+impl<R: Read> Utf16Reader<R> {
+    fn new(inner: R, encoding: &'static encoding_rs::Encoding) -> Self {
+        Self {
+            inner,
+            decoder: encoding.new_decoder_with_bom_removal(),
+            input: vec![0; TRANSCODE_CHUNK_BYTES],
+            output: Vec::new(),
+            output_pos: 0,
+            finished: false,
+        }
+    }
+}
+
+impl<R: Read> Read for Utf16Reader<R> {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        while self.output_pos == self.output.len() {
+            if self.finished {
+                return Ok(0);
+            }
+            let read = self.inner.read(&mut self.input)?;
+            let last = read == 0;
+            let capacity = self
+                .decoder
+                .max_utf8_buffer_length(read)
+                .unwrap_or(read * 3 + 16);
+            self.output.resize(capacity, 0);
+            // With the worst-case output length all input is consumed in one call
+            let (_, _, written, _) = self.decoder.decode_to_utf8(&self.input[..read], &mut self.output, last);
+            self.output.truncate(written);
+            self.output_pos = 0;
+            self.finished = last;
+        }
+
+        let len = out.len().min(self.output.len() - self.output_pos);
+        out[..len].copy_from_slice(&self.output[self.output_pos..self.output_pos + len]);
+        self.output_pos += len;
+        Ok(len)
+    }
+}
+
+/// Wrap an opened log (positioned at the start) in a line reader, transparently
+/// decompressing gzip archives and converting UTF-16 logs to UTF-8. Plain UTF-8
+/// logs are read as-is.
+fn log_reader<'a, R: Read + Send + 'a>(path: &str, head: &[u8], file: R) -> Box<dyn BufRead + Send + 'a> {
+    if is_gzip(path, head) {
+        Box::new(BufReader::new(MultiGzDecoder::new(file)))
+    } else if let Some(encoding) = utf16_encoding(head) {
+        Box::new(BufReader::new(Utf16Reader::new(file, encoding)))
+    } else {
+        Box::new(BufReader::new(file))
+    }
+}
+
+/// Open a file for reading without locking the game out of it.
+/// The share mode is spelled out rather than left to std's default, so SC can keep
+/// writing, truncating and replacing Game.log while we hold a handle.
+#[cfg(windows)]
+fn open_shared(path: &Path) -> std::io::Result<File> {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    const FILE_SHARE_READ: u32 = 0x1;
+    const FILE_SHARE_WRITE: u32 = 0x2;
+    const FILE_SHARE_DELETE: u32 = 0x4;
+
+    std::fs::OpenOptions::new()
+        .read(true)
+        .share_mode(FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE)
+        .open(path)
+}
+
+/// Open a file for reading (Unix never locks readers out)
+#[cfg(not(windows))]
+fn open_shared(path: &Path) -> std::io::Result<File> {
+    File::open(path)
+}
+
+/// The file a log path really names, following symlinks. A path that can't be resolved
+/// (missing, or a share that is down) is returned as given, so the open reports why.
+fn resolve_log_path(path: impl AsRef<Path>) -> PathBuf {
+    let path = path.as_ref();
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Open a log file for reading. A directory is rejected up front: Linux would open
+/// it and fail on the first read, Windows would report a misleading access error.
+/// Symlinks are resolved first, and logs on a network share are retried with backoff
+/// (see remote::open_with_retry).
+fn open_log_file(path: impl AsRef<Path>) -> Result<File, LogError> {
+    let open = |path: &Path| -> Result<File, LogError> {
+        if std::fs::metadata(path)?.is_dir() {
+            return Err(LogError::NotAFile);
+        }
+        Ok(retry_transient(&TRANSIENT_RETRY_DELAYS, || open_shared(path))?)
+    };
+
+    let path = resolve_log_path(path);
+    if remote::is_remote(&path) {
+        remote::open_with_retry(&path, open)
+    } else {
+        open(&path)
+    }
+}
+
+/// Open a log for line reading (plain text or gzip)
+fn open_log_reader(path: &str) -> Result<Box<dyn BufRead + Send>, LogError> {
+    let mut file = open_log_file(path)?;
+    let head = read_log_head(&mut file)?;
+    Ok(log_reader(path, &head, file))
+}
+
+/// Record the current head of `path` and report whether it differs from the head
+/// seen on the previous read (only the overlapping prefix is compared, so a file
+/// that is still filling its first bytes is not mistaken for a new one)
+fn head_changed(path: &str, head: &[u8]) -> bool {
+    let mut heads = LOG_HEADS.lock().unwrap();
+    let changed = heads.get(path).is_some_and(|previous| {
+        let common = previous.len().min(head.len());
+        previous[..common] != head[..common]
+    });
+    heads.insert(path.to_string(), head.to_vec());
+    changed
+}
+
+/// Payload of the `scan-progress` event
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct ScanProgress {
+    path: String,
+    lines_scanned: usize,
+    /// Total line count extrapolated from the average bytes per line so far
+    total_estimate: usize,
+    percent: u8,
+}
+
+/// Logs at least this large report `scan-progress` while read_log_update runs
+const PROGRESS_MIN_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Lines between progress reports (per scanning thread)
+const PROGRESS_INTERVAL_LINES: usize = 50_000;
+
+/// Aggregates line and byte counts from one or more scanning threads and reports
+/// the combined progress
+struct ProgressReporter<'a> {
+    path: &'a str,
+    total_bytes: u64,
+    lines_scanned: AtomicUsize,
+    bytes_scanned: AtomicU64,
+    report: &'a (dyn Fn(ScanProgress) + Sync),
+}
+
+impl<'a> ProgressReporter<'a> {
+    fn new(path: &'a str, total_bytes: u64, report: &'a (dyn Fn(ScanProgress) + Sync)) -> Self {
+        Self {
+            path,
+            total_bytes,
+            lines_scanned: AtomicUsize::new(0),
+            bytes_scanned: AtomicU64::new(0),
+            report,
+        }
+    }
+
+    fn record(&self, lines: usize, bytes: u64) {
+        let lines_scanned = self.lines_scanned.fetch_add(lines, Ordering::Relaxed) + lines;
+        let bytes_scanned = self.bytes_scanned.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if bytes_scanned == 0 || self.total_bytes == 0 {
+            return;
+        }
+
+        let total_estimate = (lines_scanned as u128 * self.total_bytes as u128 / bytes_scanned as u128) as usize;
+        let percent = (bytes_scanned.min(self.total_bytes) * 100 / self.total_bytes) as u8;
+        (self.report)(ScanProgress {
+            path: self.path.to_string(),
+            lines_scanned,
+            total_estimate,
+            percent,
+        });
+    }
+}
+
+/// Accumulates the result of a read_log_update pass one line at a time,
+/// so the line-index and byte-offset variants share the same filtering logic
+struct LogScanner {
     from_line: usize,
+    /// When the scanner was created, for ReadStats::duration_ms
+    started: Instant,
     extract_player_name: bool,
     extract_patterns: bool,
-) -> Result<LogUpdate, String> {
-    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
-    let reader = BufReader::new(file);
+    line_count: usize,
+    player_name: Option<String>,
+    new_lines: Vec<String>,
+    lines_scanned: usize,
+    // Pattern extraction state
+    patterns: Vec<RawLogPattern>,
+    seen_signatures: HashSet<String>,
+    cancelled: bool,
+    /// Lines that contained invalid UTF-8 (decoded lossily)
+    lossy_lines: usize,
+    /// The input ended in a line without a newline, which was not consumed
+    partial_line: bool,
+    /// Event types to return (empty = all)
+    event_types: HashSet<String>,
+    player_name_config: PlayerNameConfig,
+    /// Timestamp/severity regexes and markers lines are read with
+    profile: Arc<profiles::LogProfile>,
+    unparsed: Vec<events::UnparsedEvent>,
+    truncated_lines: Vec<usize>,
+    line_numbers: Vec<usize>,
+}
 
-    let mut line_count = 0;
-    let mut player_name: Option<String> = None;
-    let mut new_lines = Vec::new();
-    let mut lines_scanned = 0usize;
+impl LogScanner {
+    fn new(from_line: usize, extract_player_name: bool, extract_patterns: bool) -> Self {
+        Self {
+            from_line,
+            started: Instant::now(),
+            extract_player_name,
+            extract_patterns,
+            line_count: 0,
+            player_name: None,
+            new_lines: Vec::new(),
+            lines_scanned: 0,
+            patterns: Vec::new(),
+            seen_signatures: HashSet::new(),
+            cancelled: false,
+            lossy_lines: 0,
+            partial_line: false,
+            event_types: HashSet::new(),
+            player_name_config: player_name_config(),
+            profile: profiles::default_profile(),
+            unparsed: Vec::new(),
+            truncated_lines: Vec::new(),
+            line_numbers: Vec::new(),
+        }
+    }
 
-    // Pattern extraction state
-    let mut patterns: Vec<RawLogPattern> = Vec::new();
-    let mut seen_signatures: HashSet<String> = HashSet::new();
+    /// Only collect marker-matched lines of these event types (empty = all)
+    fn with_event_types(mut self, event_types: HashSet<String>) -> Self {
+        self.event_types = event_types;
+        self
+    }
+
+    /// Read lines in the format of `profile` instead of Star Citizen's
+    fn with_profile(mut self, profile: Arc<profiles::LogProfile>) -> Self {
+        self.profile = profile;
+        self
+    }
 
-    for line in reader.lines() {
-        let line = line.map_err(|e| format!("Failed to read line: {}", e))?;
+    /// Whether a marker-matched line passes the event type filter
+    fn wants_event_type(&self, line: &str) -> bool {
+        self.event_types.is_empty() || self.event_types.contains(&events::classify_line(line))
+    }
 
-        // Extract player name if requested (keep updating for most recent)
-        if extract_player_name && line.contains("AccountLoginCharacterStatus_Character") {
-            if let Some(start) = line.find("name ") {
-                let name_start = start + 5;
-                if let Some(end) = line[name_start..].find(" - ") {
-                    player_name = Some(line[name_start..name_start + end].to_string());
+    /// Feed every remaining line of `reader` through push_line, stopping early if the
+    /// read is cancelled. Returns the byte offset reached, starting from `offset`.
+    /// A final line without a newline is still being written: it is not consumed and
+    /// the offset stays before it.
+    fn read_from<R: BufRead>(
+        &mut self,
+        reader: &mut R,
+        mut offset: u64,
+        cancellation: &ReadCancellation,
+        progress: Option<&ProgressReporter>,
+    ) -> Result<u64, LogError> {
+        let mut buf = Vec::new();
+        let mut lines_read = 0usize;
+        let mut unreported_bytes = 0u64;
+
+        loop {
+            if cancellation.should_stop(lines_read) {
+                self.cancelled = true;
+                break;
+            }
+            let bytes_read = read_raw_line(reader, &mut buf)?;
+            if bytes_read == 0 {
+                break;
+            }
+            // read_raw_line strips the newline, so an unterminated line keeps its full length
+            if bytes_read == buf.len() {
+                self.partial_line = true;
+                break;
+            }
+            offset += bytes_read as u64;
+            lines_read += 1;
+            unreported_bytes += bytes_read as u64;
+            if let Some(progress) = progress {
+                if lines_read.is_multiple_of(PROGRESS_INTERVAL_LINES) {
+                    progress.record(PROGRESS_INTERVAL_LINES, std::mem::take(&mut unreported_bytes));
                 }
             }
+            let line = decode_line(&buf);
+            if let Cow::Owned(_) = line {
+                self.lossy_lines += 1;
+            }
+            self.push_line(&line);
+        }
+
+        Ok(offset)
+    }
+
+    /// Process one decoded line. Only lines that are kept get copied into a String.
+    fn push_line(&mut self, line: &str) {
+        // Extract player name if requested (keep updating for most recent)
+        if self.extract_player_name {
+            if let Some(name) = extract_player_name(line, &self.player_name_config) {
+                self.player_name = Some(name);
+            }
         }
 
         // Collect only lines that contain event markers (pre-filter for JavaScript)
-        if line_count >= from_line {
-            lines_scanned += 1;
+        if self.line_count >= self.from_line {
+            self.lines_scanned += 1;
+            let line = truncate_line(line, MAX_LINE_BYTES);
+            let line = line.as_ref();
 
             // Extract pattern if enabled (dedupe by signature within this file read)
-            if extract_patterns {
-                if let Some(pattern) = extract_log_pattern(&line) {
-                    if !seen_signatures.contains(&pattern.signature) {
-                        seen_signatures.insert(pattern.signature.clone());
-                        patterns.push(pattern);
+            if self.extract_patterns {
+                if let Some(pattern) = extract_log_pattern(line, &self.profile) {
+                    if !self.seen_signatures.contains(&pattern.signature) {
+                        self.seen_signatures.insert(pattern.signature.clone());
+                        self.patterns.push(pattern);
                     }
                 }
             }
 
-            if contains_event_marker(&line) {
-                new_lines.push(line);
+            if self.profile.contains_marker(line) && self.wants_event_type(line) {
+                // Parser coverage is checked alongside pattern discovery
+                if self.extract_patterns {
+                    self.unparsed.extend(events::check_unparsed(line, self.line_count));
+                }
+                // Only a cut line (ending in the marker) can exceed the limit
+                if line.len() > MAX_LINE_BYTES {
+                    self.truncated_lines.push(self.line_count);
+                }
+                self.new_lines.push(line.to_string());
+                self.line_numbers.push(self.line_count);
             }
         }
 
-        line_count += 1;
+        self.line_count += 1;
     }
 
-    // Debug telemetry (only in debug builds)
-    #[cfg(debug_assertions)]
-    if lines_scanned > 0 {
-        let filtered_ratio = if lines_scanned > 0 {
-            ((lines_scanned - new_lines.len()) as f64 / lines_scanned as f64 * 100.0) as u32
-        } else {
-            0
-        };
-        println!(
-            "[Rust] read_log_update: scanned {} lines, {} matched markers ({}% filtered out), {} unique patterns",
-            lines_scanned,
-            new_lines.len(),
-            filtered_ratio,
-            patterns.len()
-        );
+    /// Append the results of a scanner that read the lines following this one's.
+    /// Only valid for scanners started at line 0 (chunk-relative line indices).
+    fn merge(&mut self, next: LogScanner) {
+        self.unparsed.extend(next.unparsed.into_iter().map(|mut event| {
+            event.line_number += self.line_count;
+            event
+        }));
+        self.truncated_lines
+            .extend(next.truncated_lines.into_iter().map(|line| line + self.line_count));
+        self.line_numbers
+            .extend(next.line_numbers.into_iter().map(|line| line + self.line_count));
+        self.line_count += next.line_count;
+        if next.player_name.is_some() {
+            self.player_name = next.player_name;
+        }
+        self.new_lines.extend(next.new_lines);
+        self.lines_scanned += next.lines_scanned;
+        for pattern in next.patterns {
+            if self.seen_signatures.insert(pattern.signature.clone()) {
+                self.patterns.push(pattern);
+            }
+        }
+        self.cancelled |= next.cancelled;
+        self.lossy_lines += next.lossy_lines;
+        self.partial_line |= next.partial_line;
     }
 
-    // Warn if no markers matched in a large file (potential marker coverage issue)
-    #[cfg(debug_assertions)]
-    if lines_scanned > 1000 && new_lines.is_empty() {
-        eprintln!(
-            "[Rust] Warning: No event markers matched in {} lines. \
-             Check if EVENT_MARKERS are up-to-date with Star Citizen log format.",
-            lines_scanned
-        );
+    /// Drop patterns already reported for `path` by earlier reads (in this session or
+    /// earlier ones) and remember the rest
+    fn drop_known_patterns(&mut self, path: &str) {
+        known_patterns::drop_known(path, &mut self.patterns);
     }
 
-    // Warn if player name extraction was requested but failed
-    #[cfg(debug_assertions)]
-    if extract_player_name && player_name.is_none() && line_count > 100 {
-        eprintln!(
-            "[Rust] Warning: Could not extract player name from {} lines. \
-             Check if AccountLoginCharacterStatus_Character format has changed.",
-            line_count
-        );
+    fn read_stats(&self) -> ReadStats {
+        ReadStats {
+            lines_scanned: self.lines_scanned,
+            matched: self.new_lines.len(),
+            unique_patterns: self.patterns.len(),
+            duration_ms: self.started.elapsed().as_millis() as u64,
+        }
     }
 
-    Ok(LogUpdate {
-        line_count,
-        player_name,
-        new_lines,
-        patterns,
+    fn finish(self, end_offset: u64) -> LogUpdate {
+        *LAST_READ_STATS.lock().unwrap() = self.read_stats();
+
+        // Debug telemetry (only in debug builds)
+        #[cfg(debug_assertions)]
+        if self.lines_scanned > 0 {
+            let filtered_ratio = ((self.lines_scanned - self.new_lines.len()) as f64
+                / self.lines_scanned as f64
+                * 100.0) as u32;
+            println!(
+                "[Rust] read_log_update: scanned {} lines, {} matched markers ({}% filtered out), {} unique patterns",
+                self.lines_scanned,
+                self.new_lines.len(),
+                filtered_ratio,
+                self.patterns.len()
+            );
+        }
+
+        // Report lines that needed lossy decoding (corrupt bytes, usually from a crash)
+        #[cfg(debug_assertions)]
+        if self.lossy_lines > 0 {
+            eprintln!(
+                "[Rust] Warning: {} lines contained invalid UTF-8 and were decoded lossily",
+                self.lossy_lines
+            );
+        }
+
+        // Warn if no markers matched in a large file (potential marker coverage issue)
+        #[cfg(debug_assertions)]
+        if self.lines_scanned > 1000 && self.new_lines.is_empty() {
+            eprintln!(
+                "[Rust] Warning: No event markers matched in {} lines. \
+                 Check if EVENT_MARKERS are up-to-date with Star Citizen log format.",
+                self.lines_scanned
+            );
+        }
+
+        // Structured parsers that no longer match the log format
+        #[cfg(debug_assertions)]
+        if !self.unparsed.is_empty() {
+            eprintln!(
+                "[Rust] Warning: {} marker-matched lines could not be parsed (first: {})",
+                self.unparsed.len(),
+                self.unparsed[0].event_type
+            );
+        }
+
+        // Warn if player name extraction was requested but failed
+        #[cfg(debug_assertions)]
+        if self.extract_player_name && self.player_name.is_none() && self.line_count > 100 {
+            eprintln!(
+                "[Rust] Warning: Could not extract player name from {} lines. \
+                 Check if AccountLoginCharacterStatus_Character format has changed.",
+                self.line_count
+            );
+        }
+
+        LogUpdate {
+            line_count: self.line_count + self.partial_line as usize,
+            committed_line_count: self.line_count,
+            player_name: self.player_name,
+            new_lines: self.new_lines,
+            patterns: self.patterns,
+            end_offset,
+            rotated: false,
+            cancelled: self.cancelled,
+            unparsed: self.unparsed,
+            truncated_lines: self.truncated_lines,
+            line_numbers: self.line_numbers,
+        }
+    }
+}
+
+/// Plain-text logs at least this large are scanned in parallel on a cold start
+/// (about 250k lines of typical Game.log output)
+const PARALLEL_SCAN_MIN_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Split `0..len` into up to `parts` byte ranges that each start at a line boundary
+fn chunk_boundaries(file: &mut File, len: u64, parts: usize) -> Result<Vec<u64>, LogError> {
+    let mut boundaries = vec![0];
+    let mut reader = BufReader::new(file);
+    let mut skipped = Vec::new();
+
+    for i in 1..parts as u64 {
+        let target = len * i / parts as u64;
+        if target <= *boundaries.last().unwrap() {
+            continue;
+        }
+        // Move forward to the start of the next line
+        reader.seek(SeekFrom::Start(target))?;
+        skipped.clear();
+        let boundary = target + reader.read_until(b'\n', &mut skipped)? as u64;
+        if boundary < len && boundary > *boundaries.last().unwrap() {
+            boundaries.push(boundary);
+        }
+    }
+
+    boundaries.push(len);
+    Ok(boundaries)
+}
+
+/// Scan a whole plain-text log with one thread per newline-aligned byte range,
+/// merging the per-chunk results in file order. Returns the merged scanner and the
+/// byte offset it reached. Merging stops after a chunk that was cancelled or ended in
+/// a partial line, since the chunks after it don't continue where it stopped.
+fn scan_parallel(
+    path: &str,
+    len: u64,
+    parts: usize,
+    new_scanner: &(dyn Fn() -> LogScanner + Sync),
+    cancellation: &ReadCancellation,
+    progress: Option<&ProgressReporter>,
+) -> Result<(LogScanner, u64), LogError> {
+    let boundaries = chunk_boundaries(&mut open_log_file(path)?, len, parts)?;
+
+    let results: Vec<Result<(LogScanner, u64), LogError>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = boundaries
+            .windows(2)
+            .map(|range| {
+                let (start, end) = (range[0], range[1]);
+                scope.spawn(move || {
+                    let mut file = open_log_file(path)?;
+                    file.seek(SeekFrom::Start(start))?;
+                    let mut reader = BufReader::new(file.take(end - start));
+                    let mut scanner = new_scanner();
+                    let offset = scanner.read_from(&mut reader, start, cancellation, progress)?;
+                    Ok((scanner, offset))
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    let mut chunks = results.into_iter();
+    let (mut scanner, mut offset) = chunks.next().unwrap()?;
+    for chunk in chunks {
+        if scanner.cancelled || scanner.partial_line {
+            break;
+        }
+        let (next, next_offset) = chunk?;
+        scanner.merge(next);
+        offset = next_offset;
+    }
+    Ok((scanner, offset))
+}
+
+/// Read log file in a single pass - returns line count, player name (optional), filtered new lines, and patterns
+/// Only returns lines that contain event markers (95% reduction in data sent to JavaScript)
+///
+/// `rotated` is set when the file has fewer lines than `from_line` or its first bytes
+/// changed since the previous read (SC overwrote Game.log on restart); the caller
+/// should then re-read from line 0.
+///
+/// Pass a unique `token` to make the read abortable via cancel_read.
+///
+/// `event_types` limits `new_lines` to those categories (see events::MARKER_EVENT_TYPES),
+/// e.g. `["actor_death", "fatal_collision"]` for a kill feed. Empty or omitted returns all.
+///
+/// `profile_id` reads the log in a registered format profile instead of Star Citizen's.
+///
+/// Plain-text logs of PROGRESS_MIN_BYTES or more emit `scan-progress` events while scanning.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn read_log_update(
+    path: &str,
+    from_line: usize,
+    extract_player_name: bool,
+    extract_patterns: bool,
+    token: Option<String>,
+    event_types: Option<Vec<String>>,
+    profile_id: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<LogUpdate, LogError> {
+    let report = |progress: ScanProgress| {
+        if let Err(e) = app.emit("scan-progress", progress) {
+            eprintln!("[Rust] Failed to emit scan-progress event: {}", e);
+        }
+    };
+    let update = scan_log_update(
+        path,
+        from_line,
+        extract_player_name,
+        extract_patterns,
+        token,
+        event_types,
+        profile_id,
+        Some(&report),
+    )?;
+    // Other reads leave saving to the next one of these or to app exit
+    if !update.patterns.is_empty() {
+        if let Err(e) = known_patterns::save_known_patterns(&app) {
+            eprintln!("[Rust] Failed to save known patterns: {}", e);
+        }
+    }
+    Ok(update)
+}
+
+/// One file to read in a read_log_updates batch (same flags as read_log_update)
+#[derive(serde::Deserialize, Debug)]
+pub struct ReadRequest {
+    path: String,
+    #[serde(default)]
+    from_line: usize,
+    #[serde(default)]
+    extract_player_name: bool,
+    #[serde(default)]
+    extract_patterns: bool,
+    #[serde(default)]
+    event_types: Option<Vec<String>>,
+    #[serde(default)]
+    profile_id: Option<String>,
+}
+
+/// Result of one ReadRequest: the update, or why that file couldn't be read
+#[derive(serde::Serialize)]
+pub struct ReadResponse {
+    path: String,
+    update: Option<LogUpdate>,
+    error: Option<LogError>,
+}
+
+/// Read several logs (e.g. LIVE and PTU) in one IPC round trip, in parallel.
+/// Responses are in request order; a failing file doesn't fail the others.
+#[tauri::command]
+fn read_log_updates(requests: Vec<ReadRequest>) -> Vec<ReadResponse> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = requests
+            .into_iter()
+            .map(|request| {
+                scope.spawn(move || {
+                    let result = scan_log_update(
+                        &request.path,
+                        request.from_line,
+                        request.extract_player_name,
+                        request.extract_patterns,
+                        None,
+                        request.event_types,
+                        request.profile_id,
+                        None,
+                    );
+                    let (update, error) = match result {
+                        Ok(update) => (Some(update), None),
+                        Err(e) => (None, Some(e)),
+                    };
+                    ReadResponse {
+                        path: request.path,
+                        update,
+                        error,
+                    }
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("log read thread panicked"))
+            .collect()
     })
 }
 
+/// read_log_update without the AppHandle, reporting progress to `report` if given
+#[allow(clippy::too_many_arguments)]
+fn scan_log_update(
+    path: &str,
+    from_line: usize,
+    extract_player_name: bool,
+    extract_patterns: bool,
+    token: Option<String>,
+    event_types: Option<Vec<String>>,
+    profile_id: Option<String>,
+    report: Option<&(dyn Fn(ScanProgress) + Sync)>,
+) -> Result<LogUpdate, LogError> {
+    let cancellation = ReadCancellation::new(token);
+    let event_types: HashSet<String> = event_types.unwrap_or_default().into_iter().collect();
+    let profile = profiles::get_profile(profile_id.as_deref())?;
+
+    let mut file = open_log_file(path)?;
+    let file_len = file.metadata()?.len();
+    let head = read_log_head(&mut file)?;
+    let head_changed = head_changed(path, &head);
+    let decoded = needs_decoding(path, &head);
+
+    // File size can't be related to progress through the decoded text
+    let progress = report
+        .filter(|_| !decoded && file_len >= PROGRESS_MIN_BYTES)
+        .map(|report| ProgressReporter::new(path, file_len, report));
+
+    // Cold start on a large plain-text log: scan newline-aligned chunks in parallel
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    if from_line == 0 && threads > 1 && file_len >= PARALLEL_SCAN_MIN_BYTES && !decoded {
+        let new_scanner = || {
+            LogScanner::new(0, extract_player_name, extract_patterns)
+                .with_event_types(event_types.clone())
+                .with_profile(profile.clone())
+        };
+        let (mut scanner, offset) =
+            scan_parallel(path, file_len, threads, &new_scanner, &cancellation, progress.as_ref())?;
+        scanner.drop_known_patterns(path);
+        return Ok(scanner.finish(offset));
+    }
+
+    let mut reader = log_reader(path, &head, file);
+    let mut scanner = LogScanner::new(from_line, extract_player_name, extract_patterns)
+        .with_event_types(event_types)
+        .with_profile(profile);
+    let offset = scanner.read_from(&mut reader, 0, &cancellation, progress.as_ref())?;
+
+    let truncated = !scanner.cancelled && scanner.line_count < from_line;
+    scanner.drop_known_patterns(path);
+    let mut update = scanner.finish(offset);
+    update.rotated = truncated || (from_line > 0 && head_changed);
+    Ok(update)
+}
+
+/// Byte-offset variant of read_log_update - seeks straight to `from_byte` instead of
+/// re-scanning the file from the top, so each poll costs O(new bytes).
+///
+/// `line_count` counts the lines read since `from_byte` and `end_offset` is the position
+/// to pass on the next call. If the file is now shorter than `from_byte` or its head
+/// changed (log rotated or truncated by a game restart), nothing is read and `rotated`
+/// is set so the caller can restart from zero. `event_types` filters as in read_log_update.
+#[tauri::command]
+fn read_log_update_at(
+    path: &str,
+    from_byte: u64,
+    extract_player_name: bool,
+    extract_patterns: bool,
+    token: Option<String>,
+    event_types: Option<Vec<String>>,
+    profile_id: Option<String>,
+) -> Result<LogUpdate, LogError> {
+    let cancellation = ReadCancellation::new(token);
+    let profile = profiles::get_profile(profile_id.as_deref())?;
+
+    let mut file = open_log_file(path)?;
+    let file_len = file.metadata()?.len();
+
+    let head = read_log_head(&mut file)?;
+    let head_changed = head_changed(path, &head);
+    let decoded = needs_decoding(path, &head);
+
+    let mut scanner = LogScanner::new(0, extract_player_name, extract_patterns)
+        .with_event_types(event_types.unwrap_or_default().into_iter().collect())
+        .with_profile(profile);
+
+    // File size says nothing about decoded offsets, so gzip archives and UTF-16 logs
+    // are checked for truncation while skipping ahead instead
+    if (!decoded && file_len < from_byte) || (from_byte > 0 && head_changed) {
+        let mut update = scanner.finish(0);
+        update.rotated = true;
+        return Ok(update);
+    }
+
+    let mut reader = if decoded {
+        // Decoded offsets can't be seeked to - decode and discard up to from_byte
+        let mut reader = log_reader(path, &head, file);
+        let skipped = std::io::copy(&mut reader.by_ref().take(from_byte), &mut std::io::sink())?;
+        if skipped < from_byte {
+            let mut update = scanner.finish(0);
+            update.rotated = true;
+            return Ok(update);
+        }
+        reader
+    } else {
+        file.seek(SeekFrom::Start(from_byte))?;
+        log_reader(path, &head, file)
+    };
+
+    let offset = scanner.read_from(&mut reader, from_byte, &cancellation, None)?;
+    scanner.drop_known_patterns(path);
+    Ok(scanner.finish(offset))
+}
+
+/// Flags of open_and_read, as for read_log_update
+#[derive(serde::Deserialize, Debug, Default)]
+pub struct ReadFlags {
+    #[serde(default)]
+    extract_player_name: bool,
+    #[serde(default)]
+    extract_patterns: bool,
+    #[serde(default)]
+    event_types: Option<Vec<String>>,
+    #[serde(default)]
+    profile_id: Option<String>,
+}
+
+/// Response from open_and_read: metadata and update taken from the same read
+#[derive(serde::Serialize)]
+pub struct LogSnapshot {
+    metadata: LogMetadata,
+    update: LogUpdate,
+}
+
+/// get_log_metadata and read_log_update in one pass over one file handle, so the
+/// metadata's `line_count` is exactly the lines the update covers. With separate calls
+/// lines SC appends in between are counted by one and not the other, and a cursor
+/// built from both skips events.
+///
+/// Plain-text logs are read only up to the size taken from the handle when it was
+/// opened; lines appended during the read come with the next call.
+#[tauri::command]
+fn open_and_read(path: &str, from_line: usize, flags: Option<ReadFlags>) -> Result<LogSnapshot, LogError> {
+    let flags = flags.unwrap_or_default();
+    let profile = profiles::get_profile(flags.profile_id.as_deref())?;
+
+    let mut file = open_log_file(path)?;
+    let file_metadata = file.metadata()?;
+    let start = read_file_start(&mut file, header::HEADER_BYTES)?;
+    let head = &start[..start.len().min(LOG_HEAD_BYTES)];
+    // The banner is written once when the log starts, so the start of the file read
+    // here can't disagree with the lines read below
+    let game_version = header::game_version_from(log_reader(path, head, std::io::Cursor::new(&start[..])));
+    let head_changed = head_changed(path, head);
+    let mut reader: Box<dyn BufRead + Send> = if needs_decoding(path, head) {
+        log_reader(path, head, file)
+    } else {
+        Box::new(BufReader::new(file.take(file_metadata.len())))
+    };
+
+    // The player name is always needed for the metadata
+    let mut scanner = LogScanner::new(from_line, true, flags.extract_patterns)
+        .with_event_types(flags.event_types.unwrap_or_default().into_iter().collect())
+        .with_profile(profile);
+    let offset = scanner.read_from(&mut reader, 0, &ReadCancellation::new(None), None)?;
+
+    let truncated = scanner.line_count < from_line;
+    scanner.drop_known_patterns(path);
+    let mut update = scanner.finish(offset);
+    update.rotated = truncated || (from_line > 0 && head_changed);
+
+    let metadata = LogMetadata {
+        line_count: update.line_count,
+        committed_line_count: update.committed_line_count,
+        player_name: update.player_name.clone(),
+        modified_ms: modified_ms(&file_metadata),
+        size_bytes: file_metadata.len(),
+        channel: game_version.as_deref().and_then(header::release_channel),
+        game_version,
+    };
+    if !flags.extract_player_name {
+        update.player_name = None;
+    }
+    Ok(LogSnapshot { metadata, update })
+}
+
+/// Modification time of a file as Unix epoch milliseconds
+fn modified_ms(metadata: &std::fs::Metadata) -> Option<i64> {
+    let modified = metadata.modified().ok()?;
+    let since_epoch = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    i64::try_from(since_epoch.as_millis()).ok()
+}
+
 /// Get log file metadata (line count and player name) in a single pass
 /// Uses BufReader for memory-efficient streaming
 #[tauri::command]
-fn get_log_metadata(path: &str) -> Result<LogMetadata, String> {
-    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
-    let reader = BufReader::new(file);
+fn get_log_metadata(path: &str) -> Result<LogMetadata, LogError> {
+    let mut reader = open_log_reader(path)?;
+    let file_metadata = std::fs::metadata(path)?;
 
     let mut line_count = 0;
+    let mut committed_line_count = 0;
     let mut player_name: Option<String> = None;
+    let mut buf = Vec::new();
+    let config = player_name_config();
 
     // Read through file once, counting lines and finding player name
-    for line in reader.lines() {
-        let line = line.map_err(|e| format!("Failed to read line: {}", e))?;
+    loop {
+        let bytes_read = read_raw_line(&mut reader, &mut buf)?;
+        if bytes_read == 0 {
+            break;
+        }
+        let line = decode_line(&buf);
+        line_count += 1;
+        // read_raw_line strips the newline, so only a partial line keeps its full length
+        if bytes_read > buf.len() {
+            committed_line_count += 1;
+        }
+
+        // Look for player name in AccountLoginCharacterStatus_Character events
+        // Keep updating to get the MOST RECENT login (handles multiple sessions)
+        if let Some(name) = extract_player_name(&line, &config) {
+            player_name = Some(name);
+        }
+    }
+
+    let game_version = header::read_game_version(path)?;
+    Ok(LogMetadata {
+        line_count,
+        committed_line_count,
+        player_name,
+        modified_ms: modified_ms(&file_metadata),
+        size_bytes: file_metadata.len(),
+        channel: game_version.as_deref().and_then(header::release_channel),
+        game_version,
+    })
+}
+
+/// One character login within a log
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct Session {
+    player_name: String,
+    /// Line of the login event
+    start_line: usize,
+    start_ts_ms: Option<i64>,
+    /// Line after the session's last line (next login, or the total line count)
+    end_line: usize,
+}
+
+/// Split a log into character sessions, one per AccountLoginCharacterStatus_Character
+/// line. Lines before the first login belong to no session.
+#[tauri::command]
+fn read_sessions(path: &str) -> Result<Vec<Session>, LogError> {
+    let mut reader = open_log_reader(path)?;
+
+    let mut sessions: Vec<Session> = Vec::new();
+    let mut buf = Vec::new();
+    let mut line_count = 0;
+    let config = player_name_config();
+
+    while read_raw_line(&mut reader, &mut buf)? > 0 {
+        let line = decode_line(&buf);
+        if let Some(player_name) = extract_player_name(&line, &config) {
+            if let Some(previous) = sessions.last_mut() {
+                previous.end_line = line_count;
+            }
+            sessions.push(Session {
+                player_name,
+                start_line: line_count,
+                start_ts_ms: events::parse_timestamp(&line),
+                end_line: line_count,
+            });
+        }
+        line_count += 1;
+    }
+
+    if let Some(last) = sessions.last_mut() {
+        last.end_line = line_count;
+    }
+    Ok(sessions)
+}
+
+/// read_log_update restricted to the newest character session
+#[derive(serde::Serialize)]
+struct CurrentSessionUpdate {
+    /// Line of the last character login, None if the log has none yet
+    session_start_line: Option<usize>,
+    #[serde(flatten)]
+    update: LogUpdate,
+}
+
+/// Marker-matched lines of the current character's session only: from the last
+/// AccountLoginCharacterStatus_Character line (or `from_line`, if later) onward, so
+/// a log spanning several sessions doesn't re-surface a previous character's kills.
+/// Without any login the whole log from `from_line` is returned.
+#[tauri::command]
+fn read_current_session(path: &str, from_line: usize) -> Result<CurrentSessionUpdate, LogError> {
+    let session_start_line = read_sessions(path)?.pop().map(|session| session.start_line);
+    let start = from_line.max(session_start_line.unwrap_or(0));
+    let update = scan_log_update(path, start, true, false, None, None, None, None)?;
+    Ok(CurrentSessionUpdate {
+        session_start_line,
+        update,
+    })
+}
+
+/// Dry-run pattern extraction over the first `max_lines` lines, for eyeballing the
+/// structure of a new log or profile. Patterns are deduped by signature as in
+/// read_log_update, but nothing is recorded as reported for the file.
+#[tauri::command]
+fn sample_patterns(
+    path: &str,
+    max_lines: usize,
+    profile_id: Option<String>,
+) -> Result<Vec<RawLogPattern>, LogError> {
+    let profile = profiles::get_profile(profile_id.as_deref())?;
+    let mut reader = open_log_reader(path)?;
+    let mut scanner = LogScanner::new(0, false, true).with_profile(profile);
+    let mut buf = Vec::new();
+
+    while scanner.line_count < max_lines && read_raw_line(&mut reader, &mut buf)? > 0 {
+        scanner.push_line(&decode_line(&buf));
+    }
+
+    Ok(scanner.patterns)
+}
+
+/// Everything the reader extracts from one line, for the log inspector
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct LineExplanation {
+    /// ISO timestamp as written in the log
+    pub timestamp: Option<String>,
+    pub severity: Option<String>,
+    pub event_name: Option<String>,
+    pub teams: Vec<String>,
+    pub subsystems: Vec<String>,
+    /// Pattern signature, None for lines without a leading timestamp
+    pub signature: Option<String>,
+    /// First literal marker (custom list, or the compiled defaults) found in the line
+    pub matched_marker: Option<String>,
+    /// Event type of the first marker rule matching the line
+    pub matched_rule: Option<String>,
+    /// Matched, but muted via set_disabled_markers
+    pub disabled: bool,
+    /// Whether reads report the line
+    pub captured: bool,
+}
+
+/// First marker of the active literal list contained in a line
+fn matched_marker(line: &str, custom_markers: &[String]) -> Option<String> {
+    if custom_markers.is_empty() {
+        EVENT_MARKERS.iter().find(|marker| line.contains(*marker)).map(|marker| marker.to_string())
+    } else {
+        custom_markers.iter().find(|marker| line.contains(marker.as_str())).cloned()
+    }
+}
+
+/// Break one raw line into the fields pattern extraction sees, and say which marker
+/// (if any) made it pass the pre-filter, to diagnose why a line was or wasn't captured
+#[tauri::command]
+fn explain_line(line: String) -> LineExplanation {
+    let pattern = extract_log_pattern(&line, &profiles::default_profile());
+    let matched_marker = matched_marker(&line, &CUSTOM_EVENT_MARKERS.lock().unwrap());
+    let matched_rule = markers::find_rule(&line).map(|rule| rule.event_type);
+    let disabled = (matched_marker.is_some() || matched_rule.is_some())
+        && is_marker_disabled(&line, &DISABLED_MARKERS.lock().unwrap());
+
+    let (severity, event_name, teams, subsystems, signature) = match pattern {
+        Some(p) => (p.severity, p.event_name, p.teams, p.subsystems, Some(p.signature)),
+        None => (None, None, Vec::new(), Vec::new(), None),
+    };
+    LineExplanation {
+        timestamp: events::extract_timestamp(&line).map(str::to_string),
+        severity,
+        event_name,
+        teams,
+        subsystems,
+        signature,
+        captured: (matched_marker.is_some() || matched_rule.is_some()) && !disabled,
+        matched_marker,
+        matched_rule,
+        disabled,
+    }
+}
+
+/// Read new lines from a file starting at a specific line position
+/// Uses BufReader for memory-efficient streaming
+#[tauri::command]
+fn read_log_lines_from(path: &str, from_line: usize) -> Result<Vec<String>, LogError> {
+    let mut reader = open_log_reader(path)?;
+
+    let mut new_lines = Vec::new();
+    let mut buf = Vec::new();
+    let mut current_line = 0;
+
+    while read_raw_line(&mut reader, &mut buf)? > 0 {
+        if current_line >= from_line {
+            // Only include non-empty lines
+            let line = decode_line(&buf);
+            if !line.trim().is_empty() {
+                new_lines.push(truncate_line(&line, MAX_LINE_BYTES).into_owned());
+            }
+        }
+        current_line += 1;
+    }
+
+    Ok(new_lines)
+}
+
+/// Get line count only (fast path when player name not needed)
+#[tauri::command]
+fn get_line_count(path: &str) -> Result<usize, LogError> {
+    let mut reader = open_log_reader(path)?;
+
+    let mut line_count = 0;
+    let mut buf = Vec::new();
+    while read_raw_line(&mut reader, &mut buf)? > 0 {
         line_count += 1;
+    }
+
+    Ok(line_count)
+}
+
+/// What count_new_events found after `from_line`
+#[derive(serde::Serialize, Debug, PartialEq)]
+struct NewEventCount {
+    /// Marker-matched lines
+    new_events: usize,
+    /// Complete lines; resume from `from_line + new_lines`
+    new_lines: usize,
+}
+
+/// Number of new events since `from_line` without the lines themselves, for an unread
+/// badge on a collapsed panel. A trailing line SC is still writing is not counted.
+#[tauri::command]
+fn count_new_events(path: &str, from_line: usize) -> Result<NewEventCount, LogError> {
+    let mut count = NewEventCount {
+        new_events: 0,
+        new_lines: 0,
+    };
+    for_each_line(path, from_line, |_, line| {
+        count.new_lines += 1;
+        if contains_event_marker(line) {
+            count.new_events += 1;
+        }
+    })?;
+    Ok(count)
+}
+
+/// Forget which patterns were reported for a log, so the next read reports all of them
+/// again (used when the user reloads the log or switches character)
+#[tauri::command]
+fn clear_pattern_cache(path: &str) {
+    known_patterns::forget(path);
+}
+
+/// Forget the reported patterns of every log, releasing the memory they held
+#[tauri::command]
+fn clear_all_pattern_caches() {
+    known_patterns::forget_all();
+}
+
+/// Stop watching and forget everything read so far, so nothing of the previous
+/// user's logs carries over into the next session. Emits `watching-stopped` once done.
+#[tauri::command]
+fn shutdown_watching(app: tauri::AppHandle) -> Result<(), LogError> {
+    watcher::unwatch_all();
+    clear_all_pattern_caches();
+    known_patterns::save_known_patterns(&app)?;
+    LOG_HEADS.lock().unwrap().clear();
+    cursors::clear_read_cursors(&app)?;
+
+    if let Err(e) = app.emit("watching-stopped", ()) {
+        eprintln!("[Rust] Failed to emit watching-stopped event: {}", e);
+    }
+    Ok(())
+}
 
-        // Look for player name in AccountLoginCharacterStatus_Character events
-        // Keep updating to get the MOST RECENT login (handles multiple sessions)
-        if line.contains("AccountLoginCharacterStatus_Character") {
-            // Extract name until " - " delimiter to support hyphenated names
-            if let Some(start) = line.find("name ") {
-                let name_start = start + 5;
-                if let Some(end) = line[name_start..].find(" - ") {
-                    player_name = Some(line[name_start..name_start + end].to_string());
-                }
-            }
+/// Size of each backward read when tailing a log
+const TAIL_CHUNK_BYTES: u64 = 64 * 1024;
+
+/// Last `n` lines of a plain-text file, reading backward from the end in
+/// `chunk_bytes` chunks until enough line breaks have been seen
+fn tail_lines(file: &mut File, n: usize, chunk_bytes: u64) -> Result<Vec<String>, LogError> {
+    let mut pos = file.metadata()?.len();
+    let mut chunks: Vec<Vec<u8>> = Vec::new();
+    let mut newlines = 0usize;
+    let mut ends_with_newline = None;
+
+    while pos > 0 {
+        let chunk_len = chunk_bytes.min(pos);
+        pos -= chunk_len;
+
+        let mut chunk = vec![0u8; chunk_len as usize];
+        file.seek(SeekFrom::Start(pos))?;
+        file.read_exact(&mut chunk)?;
+
+        // A trailing newline terminates the last line rather than starting a new one
+        let trailing = *ends_with_newline.get_or_insert(chunk.last() == Some(&b'\n'));
+        newlines += chunk.iter().filter(|&&b| b == b'\n').count();
+        chunks.push(chunk);
+
+        // n separators guarantee n complete lines after the (possibly partial) first one
+        if newlines - trailing as usize >= n {
+            break;
         }
     }
 
-    Ok(LogMetadata {
-        line_count,
-        player_name,
-    })
+    let mut tail: Vec<u8> = chunks.into_iter().rev().flatten().collect();
+    if tail.last() == Some(&b'\n') {
+        tail.pop();
+    }
+    if tail.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut segments: Vec<&[u8]> = tail.split(|&b| b == b'\n').collect();
+    if pos > 0 {
+        // Stopped mid-file: the first segment may start mid-line
+        segments.remove(0);
+    }
+    let skip = segments.len().saturating_sub(n);
+
+    Ok(segments[skip..]
+        .iter()
+        .map(|segment| decode_line(segment.strip_suffix(b"\r").unwrap_or(segment)).into_owned())
+        .collect())
 }
 
-/// Read new lines from a file starting at a specific line position
-/// Uses BufReader for memory-efficient streaming
+/// Read the last `n` lines of a log in chronological order without scanning it
+/// from the start. Gzip archives cannot be read backward and are streamed instead.
 #[tauri::command]
-fn read_log_lines_from(path: &str, from_line: usize) -> Result<Vec<String>, String> {
-    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
-    let reader = BufReader::new(file);
-
-    let mut new_lines = Vec::new();
-    let mut current_line = 0;
+fn read_last_lines(path: &str, n: usize) -> Result<Vec<String>, LogError> {
+    if n == 0 {
+        return Ok(Vec::new());
+    }
 
-    for line in reader.lines() {
-        let line = line.map_err(|e| format!("Failed to read line: {}", e))?;
+    let mut file = open_log_file(path)?;
+    let head = read_log_head(&mut file)?;
+    if !needs_decoding(path, &head) {
+        return tail_lines(&mut file, n, TAIL_CHUNK_BYTES);
+    }
 
-        if current_line >= from_line {
-            // Only include non-empty lines
-            let trimmed = line.trim();
-            if !trimmed.is_empty() {
-                new_lines.push(line);
-            }
+    let mut reader = log_reader(path, &head, file);
+    let mut last = VecDeque::with_capacity(n);
+    let mut buf = Vec::new();
+    while read_raw_line(&mut reader, &mut buf)? > 0 {
+        if last.len() == n {
+            last.pop_front();
         }
-
-        current_line += 1;
+        last.push_back(decode_line(&buf).into_owned());
     }
 
-    Ok(new_lines)
+    Ok(last.into())
 }
 
-/// Get line count only (fast path when player name not needed)
-#[tauri::command]
-fn get_line_count(path: &str) -> Result<usize, String> {
-    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
-    let reader = BufReader::new(file);
+/// Cut a window of raw bytes down to the whole lines in it. `starts_mid_line`: the
+/// byte before the window isn't a line break. `ends_on_line`: the window is followed
+/// by a line break or the end of the file.
+fn whole_lines(window: &[u8], starts_mid_line: bool, ends_on_line: bool) -> &[u8] {
+    let mut window = window;
+    if starts_mid_line {
+        window = window
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(&[][..], |i| &window[i + 1..]);
+    }
+    if !ends_on_line {
+        window = window
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(&[][..], |i| &window[..=i]);
+    }
+    window
+}
 
-    Ok(reader.lines().count())
+/// The whole lines within bytes `start..start + len` of a log, for a virtualized raw
+/// log viewer. Lines cut by either end of the range are left out, and a range past
+/// the end of the file returns what is available. Offsets in gzip archives and UTF-16
+/// logs are offsets into the decoded UTF-8 text.
+#[tauri::command]
+fn read_byte_range(path: &str, start: u64, len: u64) -> Result<String, LogError> {
+    let mut file = open_log_file(path)?;
+    let head = read_log_head(&mut file)?;
+
+    // Read the byte before the range and the one after it too, to see where lines break
+    let from = start.saturating_sub(1);
+    let want = len.saturating_add(start - from);
+    let reader: Box<dyn Read> = if needs_decoding(path, &head) {
+        // Decoded offsets can't be seeked to - decode and discard up to `from`
+        let mut reader = log_reader(path, &head, file);
+        std::io::copy(&mut reader.by_ref().take(from), &mut std::io::sink())?;
+        Box::new(reader)
+    } else {
+        file.seek(SeekFrom::Start(from))?;
+        Box::new(file)
+    };
+    let mut window = Vec::new();
+    reader.take(want.saturating_add(1)).read_to_end(&mut window)?;
+
+    // The window is in memory, so its length fits a usize even where `want` doesn't
+    let want = usize::try_from(want).unwrap_or(usize::MAX);
+    let ends_on_line = match window.get(want) {
+        Some(&next) => next == b'\n',
+        None => true,
+    };
+    window.truncate(want);
+    let (starts_mid_line, window) = match (start > 0, window.split_first()) {
+        (false, _) => (false, &window[..]),
+        (true, Some((&before, rest))) => (before != b'\n', rest),
+        (true, None) => return Ok(String::new()),
+    };
+
+    Ok(decode_line(whole_lines(window, starts_mid_line, ends_on_line)).into_owned())
 }
 
 /// Check if running a debug build (for E2E test detection)
@@ -605,14 +2195,21 @@ pub fn run() {
             // Note: Deep link forwarding removed - auth now uses WebSocket push
         }))
         .plugin(tauri_plugin_updater::Builder::new().pubkey("dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IDNDMzFDRDcxMTEzQUNGMjYKUldRbXp6b1JjYzB4UEx0ODl6NkNtellkVXhNbnRUQ2QwRDY1ZGlvNWJmL0RkeVdMKzBudkM1WHoK").build())
-        .setup(|_app| {
+        .setup(|app| {
             // Note: Deep link protocol registration removed
             // Auth now uses WebSocket push from server instead of picologs:// deep links
 
+            load_event_markers(app.handle());
+            load_player_name_config(app.handle());
+            profiles::load_log_profiles(app.handle());
+            markers::load_marker_rules(app.handle());
+            markers::load_field_extractors(app.handle());
+            known_patterns::load_known_patterns(app.handle());
+
             // Open DevTools automatically in dev mode
             #[cfg(debug_assertions)]
             {
-                if let Some(main_window) = _app.get_webview_window("main") {
+                if let Some(main_window) = app.get_webview_window("main") {
                     main_window.open_devtools();
                     println!("[Dev Mode] DevTools opened for main window");
                 }
@@ -630,7 +2227,75 @@ pub fn run() {
         get_log_metadata,
         read_log_lines_from,
         get_line_count,
+        count_new_events,
+        read_sessions,
+        read_current_session,
+        sample_patterns,
+        explain_line,
+        read_last_lines,
+        read_byte_range,
         read_log_update,
+        read_log_update_at,
+        read_log_updates,
+        cancel_read,
+        get_last_read_stats,
+        clear_pattern_cache,
+        clear_all_pattern_caches,
+        shutdown_watching,
+        cursors::save_read_cursor,
+        cursors::load_read_cursor,
+        cursors::log_fingerprint,
+        discovery::find_latest_log,
+        discovery::detect_sc_log_paths,
+        discovery::grant_log_directory,
+        header::validate_log_file,
+        header::read_game_version,
+        export::export_events_csv,
+        export::export_session_json,
+        line_index::line_offset,
+        profiles::register_log_profile,
+        profiles::list_log_profiles,
+        profiles::export_profile,
+        profiles::import_profile,
+        set_event_markers,
+        set_disabled_markers,
+        set_player_name_config,
+        markers::set_marker_rules,
+        markers::add_field_extractor,
+        events::read_parsed_events,
+        events::stream_log_events,
+        events::read_inventory_events,
+        events::read_presence_events,
+        events::read_player_combat,
+        upload::upload_events,
+        stats::aggregate_stats,
+        discovery::list_log_files,
+        discovery::delete_log_file,
+        markers::validate_regex,
+        events::replay_log,
+        known_patterns::get_known_patterns,
+        stats::list_event_types,
+        open_and_read,
+        export::anonymize_log,
+        events::read_platform_events,
+        events::read_economy_events,
+        events::event_type_histogram,
+        events::marker_last_seen,
+        events::read_stamina_series,
+        timeline::merge_logs,
+        diff::diff_logs,
+        travel::read_quantum_jumps,
+        travel::read_spool_timeline,
+        stats::compute_stats,
+        stats::count_severities,
+        stats::subsystem_error_report,
+        stats::event_rate,
+        stats::detect_idle_gaps,
+        search::search_log,
+        watcher::watch_log_file,
+        watcher::read_log_blocking,
+        watcher::is_live_log,
+        watcher::unwatch_log_file,
         // Test commands (debug only)
         test_inject_auth,
         test_select_log_file,
@@ -649,12 +2314,89 @@ pub fn run() {
         get_log_metadata,
         read_log_lines_from,
         get_line_count,
-        read_log_update
+        count_new_events,
+        read_sessions,
+        read_current_session,
+        sample_patterns,
+        explain_line,
+        read_last_lines,
+        read_byte_range,
+        read_log_update,
+        read_log_update_at,
+        read_log_updates,
+        cancel_read,
+        get_last_read_stats,
+        clear_pattern_cache,
+        clear_all_pattern_caches,
+        shutdown_watching,
+        cursors::save_read_cursor,
+        cursors::load_read_cursor,
+        cursors::log_fingerprint,
+        discovery::find_latest_log,
+        discovery::detect_sc_log_paths,
+        discovery::grant_log_directory,
+        header::validate_log_file,
+        header::read_game_version,
+        export::export_events_csv,
+        export::export_session_json,
+        line_index::line_offset,
+        profiles::register_log_profile,
+        profiles::list_log_profiles,
+        profiles::export_profile,
+        profiles::import_profile,
+        set_event_markers,
+        set_disabled_markers,
+        set_player_name_config,
+        markers::set_marker_rules,
+        markers::add_field_extractor,
+        events::read_parsed_events,
+        events::stream_log_events,
+        events::read_inventory_events,
+        events::read_presence_events,
+        events::read_player_combat,
+        upload::upload_events,
+        stats::aggregate_stats,
+        discovery::list_log_files,
+        discovery::delete_log_file,
+        markers::validate_regex,
+        events::replay_log,
+        known_patterns::get_known_patterns,
+        stats::list_event_types,
+        open_and_read,
+        export::anonymize_log,
+        events::read_platform_events,
+        events::read_economy_events,
+        events::event_type_histogram,
+        events::marker_last_seen,
+        events::read_stamina_series,
+        timeline::merge_logs,
+        diff::diff_logs,
+        travel::read_quantum_jumps,
+        travel::read_spool_timeline,
+        stats::compute_stats,
+        stats::count_severities,
+        stats::subsystem_error_report,
+        stats::event_rate,
+        stats::detect_idle_gaps,
+        search::search_log,
+        watcher::watch_log_file,
+        watcher::read_log_blocking,
+        watcher::is_live_log,
+        watcher::unwatch_log_file
     ]);
 
     builder
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            // Release OS file watches before the process exits
+            if let tauri::RunEvent::Exit = event {
+                watcher::unwatch_all();
+                if let Err(e) = known_patterns::save_known_patterns(app) {
+                    eprintln!("[Rust] Failed to save known patterns: {}", e);
+                }
+            }
+        });
 }
 
 // ============================================================================
@@ -711,6 +2453,16 @@ mod tests {
         assert_eq!(sig1, sig2);
     }
 
+    #[test]
+    fn test_generate_signature_collapses_ids_in_event_name() {
+        let signature = |name: &str| generate_signature(&Some(name.to_string()), &None, &[], &[]);
+
+        assert_eq!(signature("Entity_12345"), signature("Entity_67890"));
+        assert_eq!(signature("Obj_0x1F3A::Update"), signature("Obj_0xbeef::Update"));
+        assert_eq!(signature("Entity_12345"), "Entity_#|null||");
+        assert_ne!(signature("Entity_1"), signature("Vehicle_1"));
+    }
+
     // ========================================================================
     // contains_event_marker tests
     // ========================================================================
@@ -732,6 +2484,25 @@ mod tests {
         assert!(!contains_event_marker(""));
     }
 
+    #[test]
+    fn test_matches_event_marker_custom_list_replaces_defaults() {
+        let custom = vec!["<Renamed Actor Death>".to_string()];
+
+        assert!(matches_event_marker("<Renamed Actor Death> player killed", &custom));
+        assert!(!matches_event_marker("<Actor Death> player killed", &custom));
+        // Empty list falls back to the compiled defaults
+        assert!(matches_event_marker("<Actor Death> player killed", &[]));
+    }
+
+    #[test]
+    fn test_is_marker_disabled() {
+        let disabled = vec!["<[STAMINA]".to_string()];
+
+        assert!(is_marker_disabled("<2024-01-01T12:00:00.000Z> <[STAMINA]> 0.5", &disabled));
+        assert!(!is_marker_disabled("<Actor Death> player killed", &disabled));
+        assert!(!is_marker_disabled("<[STAMINA]> 0.5", &[]));
+    }
+
     #[test]
     fn test_contains_event_marker_partial_match() {
         // "Destruction>" is a partial match marker
@@ -746,7 +2517,7 @@ mod tests {
     #[test]
     fn test_extract_log_pattern_with_timestamp() {
         let line = "<2024-01-01T12:00:00.000Z> [Notice] <TestEvent> Some log message";
-        let pattern = extract_log_pattern(line);
+        let pattern = extract_log_pattern(line, &profiles::default_profile());
 
         assert!(pattern.is_some());
         let p = pattern.unwrap();
@@ -757,7 +2528,7 @@ mod tests {
     #[test]
     fn test_extract_log_pattern_without_timestamp() {
         let line = "[Notice] <TestEvent> Some log message without timestamp";
-        let pattern = extract_log_pattern(line);
+        let pattern = extract_log_pattern(line, &profiles::default_profile());
 
         // Should return None if no timestamp
         assert!(pattern.is_none());
@@ -766,7 +2537,7 @@ mod tests {
     #[test]
     fn test_extract_log_pattern_extracts_teams() {
         let line = "<2024-01-01T12:00:00.000Z> [Team_Blue] [Team_Red] Combat event";
-        let pattern = extract_log_pattern(line).unwrap();
+        let pattern = extract_log_pattern(line, &profiles::default_profile()).unwrap();
 
         assert!(pattern.teams.contains(&"Team_Blue".to_string()));
         assert!(pattern.teams.contains(&"Team_Red".to_string()));
@@ -775,7 +2546,7 @@ mod tests {
     #[test]
     fn test_extract_log_pattern_extracts_subsystems() {
         let line = "<2024-01-01T12:00:00.000Z> [Physics] [Combat] <Event> message";
-        let pattern = extract_log_pattern(line).unwrap();
+        let pattern = extract_log_pattern(line, &profiles::default_profile()).unwrap();
 
         assert!(pattern.subsystems.contains(&"Physics".to_string()));
         assert!(pattern.subsystems.contains(&"Combat".to_string()));
@@ -784,7 +2555,7 @@ mod tests {
     #[test]
     fn test_extract_log_pattern_excludes_severity_from_subsystems() {
         let line = "<2024-01-01T12:00:00.000Z> [Notice] [Error] <Event> message";
-        let pattern = extract_log_pattern(line).unwrap();
+        let pattern = extract_log_pattern(line, &profiles::default_profile()).unwrap();
 
         // Notice and Error should NOT be in subsystems (they're severity tags)
         assert!(!pattern.subsystems.contains(&"Notice".to_string()));
@@ -794,7 +2565,7 @@ mod tests {
     #[test]
     fn test_extract_log_pattern_skips_date_events() {
         let line = "<2024-01-01T12:00:00.000Z> <2024-01-01> Some message";
-        let pattern = extract_log_pattern(line).unwrap();
+        let pattern = extract_log_pattern(line, &profiles::default_profile()).unwrap();
 
         // Should not extract dates as event names
         assert!(pattern.event_name.is_none() || !pattern.event_name.as_ref().unwrap().starts_with("20"));
@@ -806,9 +2577,9 @@ mod tests {
         let line2 = "<2024-01-01T12:00:00.000Z> [Notice] <EventA> message 2";
         let line3 = "<2024-01-01T12:00:00.000Z> [Notice] <EventB> message 3";
 
-        let p1 = extract_log_pattern(line1).unwrap();
-        let p2 = extract_log_pattern(line2).unwrap();
-        let p3 = extract_log_pattern(line3).unwrap();
+        let p1 = extract_log_pattern(line1, &profiles::default_profile()).unwrap();
+        let p2 = extract_log_pattern(line2, &profiles::default_profile()).unwrap();
+        let p3 = extract_log_pattern(line3, &profiles::default_profile()).unwrap();
 
         // Same event type should have same signature
         assert_eq!(p1.signature, p2.signature);
@@ -830,12 +2601,14 @@ mod tests {
             subsystems: vec!["Physics".to_string()],
             signature: "test-signature".to_string(),
             example_line: "example line".to_string(),
+            first_seen_ms: Some(1_704_110_400_000),
         };
 
         // Test that it serializes correctly (camelCase)
         let json = serde_json::to_string(&pattern).unwrap();
         assert!(json.contains("\"eventName\""));
         assert!(json.contains("\"exampleLine\""));
+        assert!(json.contains("\"firstSeenMs\":1704110400000"));
     }
 
     // ========================================================================
@@ -846,15 +2619,702 @@ mod tests {
     fn test_log_update_serialization() {
         let update = LogUpdate {
             line_count: 100,
+            committed_line_count: 100,
             player_name: Some("TestPlayer".to_string()),
             new_lines: vec!["line1".to_string(), "line2".to_string()],
             patterns: vec![],
+            end_offset: 0,
+            rotated: false,
+            cancelled: false,
+            unparsed: vec![],
+            truncated_lines: vec![],
+            line_numbers: vec![3, 7],
         };
 
         let json = serde_json::to_string(&update).unwrap();
         assert!(json.contains("\"line_count\":100"));
         assert!(json.contains("\"player_name\":\"TestPlayer\""));
         assert!(json.contains("\"new_lines\""));
+        assert!(json.contains("\"line_numbers\":[3,7]"));
+    }
+
+    // ========================================================================
+    // read_log_update_at tests
+    // ========================================================================
+
+    /// Write `content` to a uniquely named file in the system temp dir
+    pub(crate) fn write_temp_log(name: &str, content: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("picologs_test_{}_{}.log", name, std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_log_update_at_resumes_from_end_offset() {
+        let first = "<2024-01-01T12:00:00.000Z> <Actor Death> first\nplain line\n";
+        let path = write_temp_log("resume", first);
+        let path_str = path.to_str().unwrap();
+
+        let update = read_log_update_at(path_str, 0, false, false, None, None, None).unwrap();
+        assert_eq!(update.line_count, 2);
+        assert_eq!(update.new_lines.len(), 1);
+        assert_eq!(update.end_offset, first.len() as u64);
+        assert!(!update.rotated);
+
+        let mut content = first.to_string();
+        content.push_str("<2024-01-01T12:00:01.000Z> <SystemQuit> second\r\n");
+        std::fs::write(&path, &content).unwrap();
+
+        let update = read_log_update_at(path_str, update.end_offset, false, false, None, None, None).unwrap();
+        assert_eq!(update.line_count, 1);
+        assert_eq!(update.new_lines, vec!["<2024-01-01T12:00:01.000Z> <SystemQuit> second"]);
+        assert_eq!(update.end_offset, content.len() as u64);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_log_update_at_signals_rotation_when_file_shrinks() {
+        let path = write_temp_log("rotated", "<SystemQuit>\n");
+
+        let update = read_log_update_at(path.to_str().unwrap(), 10_000, false, false, None, None, None).unwrap();
+        assert!(update.rotated);
+        assert_eq!(update.end_offset, 0);
+        assert!(update.new_lines.is_empty());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_log_update_flags_rotation_when_head_changes() {
+        let path = write_temp_log("head", "<2024-01-01T12:00:00.000Z> Log started on session A\nline\nline\n");
+        let path_str = path.to_str().unwrap();
+
+        let update = scan_log_update(path_str, 0, false, false, None, None, None, None).unwrap();
+        assert!(!update.rotated);
+
+        // Game restarted and the new file already grew past the old line count
+        std::fs::write(&path, "<2024-01-02T08:00:00.000Z> Log started on session B\na\nb\nc\nd\n").unwrap();
+        let update = scan_log_update(path_str, 3, false, false, None, None, None, None).unwrap();
+        assert!(update.rotated);
+
+        // Subsequent reads of the same file are not flagged again
+        let update = scan_log_update(path_str, 5, false, false, None, None, None, None).unwrap();
+        assert!(!update.rotated);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_open_and_read_metadata_matches_update() {
+        let path = write_temp_log(
+            "open_and_read",
+            "<2024-01-01T12:00:00.000Z> [Notice] <AccountLoginCharacterStatus_Character> Character: geid 3 - name Pilot - state STATE_CURRENT\n\
+             <2024-01-01T12:00:01.000Z> [Notice] <SystemQuit> bye\n\
+             <2024-01-01T12:00:02.000Z> still being writ",
+        );
+
+        let snapshot = open_and_read(path.to_str().unwrap(), 1, None).unwrap();
+        assert_eq!(snapshot.metadata.line_count, snapshot.update.line_count);
+        assert_eq!(snapshot.metadata.committed_line_count, 2);
+        assert_eq!(snapshot.metadata.committed_line_count, snapshot.update.committed_line_count);
+        assert_eq!(snapshot.metadata.player_name.as_deref(), Some("Pilot"));
+        assert_eq!(snapshot.metadata.size_bytes, std::fs::metadata(&path).unwrap().len());
+        assert_eq!(snapshot.update.player_name, None);
+        assert_eq!(snapshot.update.line_numbers, vec![1]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_log_update_flags_rotation_when_file_has_fewer_lines() {
+        let path = write_temp_log("fewer_lines", "a\nb\n");
+
+        let update = scan_log_update(path.to_str().unwrap(), 50, false, false, None, None, None, None).unwrap();
+        assert!(update.rotated);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_log_update_cancelled_returns_partial_result() {
+        let content = "<SystemQuit>\n".repeat(CANCEL_CHECK_INTERVAL * 3);
+        let path = write_temp_log("cancel", &content);
+        let token = format!("cancel-test-{}", std::process::id());
+
+        cancel_read(token.clone());
+        let update = scan_log_update(path.to_str().unwrap(), 0, false, false, Some(token.clone()), None, None, None).unwrap();
+
+        assert!(update.cancelled);
+        assert!(!update.rotated);
+        assert!(update.line_count < CANCEL_CHECK_INTERVAL * 3);
+        // Token is consumed by the cancelled read
+        assert!(!CANCELLED_READS.lock().unwrap().contains(&token));
+
+        let update = scan_log_update(path.to_str().unwrap(), 0, false, false, Some(token), None, None, None).unwrap();
+        assert!(!update.cancelled);
+        assert_eq!(update.line_count, CANCEL_CHECK_INTERVAL * 3);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_commands_return_typed_errors() {
+        let missing = std::env::temp_dir().join("picologs_test_definitely_missing.log");
+        assert_eq!(get_line_count(missing.to_str().unwrap()), Err(LogError::NotFound));
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_decoded_lossily() {
+        let path = write_temp_log("invalid_utf8", "");
+        std::fs::write(&path, b"ok line\nbad \xff byte\n<SystemQuit> after\n").unwrap();
+
+        let lines = read_log_lines_from(path.to_str().unwrap(), 0).unwrap();
+        assert_eq!(lines, vec!["ok line", "bad \u{FFFD} byte", "<SystemQuit> after"]);
+
+        // The corrupt line doesn't stop the scan from reaching later events
+        let update = scan_log_update(path.to_str().unwrap(), 0, false, false, None, None, None, None).unwrap();
+        assert_eq!(update.line_count, 3);
+        assert_eq!(update.new_lines, vec!["<SystemQuit> after"]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_sessions_splits_on_logins() {
+        let content = "<2024-01-01T11:59:00.000Z> startup\n\
+                       <2024-01-01T12:00:00.000Z> AccountLoginCharacterStatus_Character - name First-Pilot - state 1\n\
+                       <2024-01-01T12:30:00.000Z> <Actor Death> something\n\
+                       <2024-01-01T13:00:00.000Z> AccountLoginCharacterStatus_Character - name Second-Pilot - state 1\n\
+                       <2024-01-01T13:05:00.000Z> <SystemQuit> quit\n";
+        let path = write_temp_log("sessions", content);
+
+        let sessions = read_sessions(path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            sessions,
+            vec![
+                Session {
+                    player_name: "First-Pilot".to_string(),
+                    start_line: 1,
+                    start_ts_ms: Some(1_704_110_400_000),
+                    end_line: 3,
+                },
+                Session {
+                    player_name: "Second-Pilot".to_string(),
+                    start_line: 3,
+                    start_ts_ms: Some(1_704_114_000_000),
+                    end_line: 5,
+                },
+            ]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_for_each_line_skips_partial_last_line() {
+        let path = write_temp_log("for_each_line", "zero\none\ntwo\nstill being writ");
+        let mut lines = Vec::new();
+        for_each_line(path.to_str().unwrap(), 1, |line_number, line| {
+            lines.push((line_number, line.to_string()));
+        })
+        .unwrap();
+        assert_eq!(lines, vec![(1, "one".to_string()), (2, "two".to_string())]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_count_new_events() {
+        let path = write_temp_log(
+            "count_new_events",
+            "<Actor Death> one\nplain\n<SystemQuit> two\n<Actor Death> still being writ",
+        );
+        let path_str = path.to_str().unwrap();
+
+        assert_eq!(
+            count_new_events(path_str, 0).unwrap(),
+            NewEventCount {
+                new_events: 2,
+                new_lines: 3,
+            }
+        );
+        assert_eq!(
+            count_new_events(path_str, 1).unwrap(),
+            NewEventCount {
+                new_events: 1,
+                new_lines: 2,
+            }
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_explain_line() {
+        let explanation = explain_line(
+            "<2024-01-01T12:00:00.000Z> [Notice] <SystemQuit> CSystem::Quit invoked [Team_Engine][Shutdown]".to_string(),
+        );
+        assert_eq!(explanation.timestamp.as_deref(), Some("2024-01-01T12:00:00.000Z"));
+        assert_eq!(explanation.severity.as_deref(), Some("Notice"));
+        assert_eq!(explanation.event_name.as_deref(), Some("SystemQuit"));
+        assert_eq!(explanation.teams, vec!["Team_Engine".to_string()]);
+        assert_eq!(explanation.subsystems, vec!["Shutdown".to_string()]);
+        assert_eq!(explanation.matched_marker.as_deref(), Some("<SystemQuit>"));
+        assert!(explanation.captured);
+
+        let plain = explain_line("no timestamp, no marker".to_string());
+        assert_eq!(plain.signature, None);
+        assert_eq!(plain.matched_marker, None);
+        assert!(!plain.captured);
+    }
+
+    #[test]
+    fn test_sample_patterns_stops_after_max_lines() {
+        let path = write_temp_log(
+            "sample_patterns",
+            "<2024-01-01T12:00:00.000Z> [Notice] <SystemQuit> one [Team_Core]\n\
+             <2024-01-01T12:00:01.000Z> [Notice] <SystemQuit> two [Team_Core]\n\
+             <2024-01-01T12:00:02.000Z> [Error] <Actor Death> three [Team_Actor]\n",
+        );
+        let path_str = path.to_str().unwrap();
+
+        let patterns = sample_patterns(path_str, 2, None).unwrap();
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].event_name.as_deref(), Some("SystemQuit"));
+        assert_eq!(sample_patterns(path_str, 100, None).unwrap().len(), 2);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_current_session_skips_earlier_sessions() {
+        let content = "<2024-01-01T12:00:00.000Z> AccountLoginCharacterStatus_Character - name First-Pilot - state 1\n\
+                       <2024-01-01T12:30:00.000Z> <Actor Death> old kill\n\
+                       <2024-01-01T13:00:00.000Z> AccountLoginCharacterStatus_Character - name Second-Pilot - state 1\n\
+                       <2024-01-01T13:05:00.000Z> <Actor Death> new kill\n";
+        let path = write_temp_log("current_session", content);
+        let path_str = path.to_str().unwrap();
+
+        let current = read_current_session(path_str, 0).unwrap();
+        assert_eq!(current.session_start_line, Some(2));
+        assert_eq!(current.update.player_name, Some("Second-Pilot".to_string()));
+        assert_eq!(current.update.line_numbers, vec![2, 3]);
+        assert_eq!(current.update.line_count, 4);
+
+        // A later cursor still wins
+        assert_eq!(read_current_session(path_str, 3).unwrap().update.line_numbers, vec![3]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_extract_player_name_keeps_hyphens_and_spaces() {
+        let config = PlayerNameConfig::default();
+        let line = |name: &str| {
+            format!("<2024-01-01T12:00:00.000Z> [Notice] <AccountLoginCharacterStatus_Character> Character: createdAt 1 - updatedAt 2 - geid 3 - accountId 4 - name {} - state STATE_CURRENT", name)
+        };
+
+        assert_eq!(extract_player_name(&line("Space-Pilot-42"), &config), Some("Space-Pilot-42".to_string()));
+        assert_eq!(extract_player_name(&line("Jean Luc"), &config), Some("Jean Luc".to_string()));
+        assert_eq!(extract_player_name("<Actor Death> name Someone - else", &config), None);
+    }
+
+    #[test]
+    fn test_extract_player_name_custom_format() {
+        let config = PlayerNameConfig {
+            marker: "<CharacterLogin>".to_string(),
+            prefix: "handle=".to_string(),
+            delimiter: ";".to_string(),
+        };
+        let line = "<2024-01-01T12:00:00.000Z> <CharacterLogin> handle=Drift-Runner; geid=3";
+
+        assert_eq!(extract_player_name(line, &config), Some("Drift-Runner".to_string()));
+        assert_eq!(extract_player_name(line, &PlayerNameConfig::default()), None);
+    }
+
+    #[test]
+    fn test_scanner_with_custom_profile() {
+        let profile = profiles::LogProfile::compile(profiles::LogProfileConfig {
+            id: "other_game".to_string(),
+            timestamp_regex: r"^\[\d{2}:\d{2}:\d{2}\] ".to_string(),
+            timestamp_format: None,
+            utc_offset_minutes: None,
+            severity_regex: r"^(INFO|WARN|ERROR):".to_string(),
+            markers: vec!["PlayerKilled".to_string()],
+        })
+        .unwrap();
+        let mut scanner = LogScanner::new(0, false, true).with_profile(Arc::new(profile));
+
+        scanner.push_line("[12:00:00] ERROR: <PlayerKilled> victim=Bob");
+        scanner.push_line("[12:00:01] INFO: <Actor Death> not a marker here");
+        let stats = scanner.read_stats();
+        assert_eq!((stats.lines_scanned, stats.matched, stats.unique_patterns), (2, 1, 2));
+        let update = scanner.finish(0);
+
+        assert_eq!(update.new_lines, vec!["[12:00:00] ERROR: <PlayerKilled> victim=Bob".to_string()]);
+        assert_eq!(update.patterns.len(), 2);
+        assert_eq!(update.patterns[0].severity.as_deref(), Some("ERROR"));
+        assert_eq!(update.patterns[0].event_name.as_deref(), Some("PlayerKilled"));
+    }
+
+    #[test]
+    fn test_read_log_updates_keeps_request_order() {
+        let live = write_temp_log("batch_live", "<2024-01-01T12:00:00.000Z> <Actor Death> live\nplain\n");
+        let ptu = write_temp_log("batch_ptu", "plain\n");
+        let request = |path: &Path| ReadRequest {
+            path: path.to_str().unwrap().to_string(),
+            from_line: 0,
+            extract_player_name: false,
+            extract_patterns: false,
+            event_types: None,
+            profile_id: None,
+        };
+        let missing = std::env::temp_dir().join("picologs_test_batch_missing.log");
+
+        let responses = read_log_updates(vec![request(&live), request(&missing), request(&ptu)]);
+
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0].update.as_ref().unwrap().line_count, 2);
+        assert_eq!(responses[1].error, Some(LogError::NotFound));
+        assert_eq!(responses[2].path, ptu.to_str().unwrap());
+        assert_eq!(responses[2].update.as_ref().unwrap().line_count, 1);
+
+        std::fs::remove_file(live).unwrap();
+        std::fs::remove_file(ptu).unwrap();
+    }
+
+    #[test]
+    fn test_oversized_line_truncated() {
+        let blob = format!("<2024-01-01T12:00:01.000Z> [Notice] <Actor Death> {}", "é".repeat(MAX_LINE_BYTES));
+        let path = write_temp_log(
+            "oversized",
+            &format!("<2024-01-01T12:00:00.000Z> <SystemQuit> short\n{}\n<SystemQuit> after\n", blob),
+        );
+
+        let update = scan_log_update(path.to_str().unwrap(), 0, false, false, None, None, None, None).unwrap();
+        assert_eq!(update.line_count, 3);
+        assert_eq!(update.truncated_lines, vec![1]);
+        assert!(update.new_lines[1].ends_with(TRUNCATION_MARKER));
+        assert!(update.new_lines[1].len() <= MAX_LINE_BYTES + TRUNCATION_MARKER.len());
+        assert!(blob.starts_with(update.new_lines[1].trim_end_matches(TRUNCATION_MARKER)));
+        assert_eq!(update.new_lines[2], "<SystemQuit> after");
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_unparsed_marker_lines_reported() {
+        let path = write_temp_log(
+            "unparsed",
+            "plain line\n\
+             <2024-01-01T12:00:00.000Z> [Notice] <Actor Death> CActor::Kill: 'Victim' [1] in zone 'zone' killed by 'Killer' [2] using 'weapon' with damage type 'Combat'\n\
+             <2024-01-01T12:00:01.000Z> [Notice] <Actor Death> format changed in a patch\n",
+        );
+        let path_str = path.to_str().unwrap();
+
+        let update = scan_log_update(path_str, 0, false, true, None, None, None, None).unwrap();
+        assert_eq!(
+            update.unparsed,
+            vec![events::UnparsedEvent {
+                event_type: "actor_death".to_string(),
+                line_number: 2,
+                raw: "<2024-01-01T12:00:01.000Z> [Notice] <Actor Death> format changed in a patch".to_string(),
+            }]
+        );
+
+        let update = scan_log_update(path_str, 0, false, false, None, None, None, None).unwrap();
+        assert!(update.unparsed.is_empty());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_directory_path_is_not_a_file() {
+        let dir = std::env::temp_dir();
+        let dir_str = dir.to_str().unwrap();
+
+        assert_eq!(get_line_count(dir_str).err(), Some(LogError::NotAFile));
+        assert_eq!(get_log_metadata(dir_str).err(), Some(LogError::NotAFile));
+        assert_eq!(read_log_update_at(dir_str, 0, false, false, None, None, None).err(), Some(LogError::NotAFile));
+    }
+
+    #[test]
+    fn test_leading_bom_is_stripped() {
+        let path = write_temp_log("bom", "\u{FEFF}<2024-01-01T12:00:00.000Z> [Notice] <Actor Death> first event\n");
+        let path_str = path.to_str().unwrap();
+
+        let update = scan_log_update(path_str, 0, false, true, None, None, None, None).unwrap();
+        assert_eq!(update.patterns.len(), 1);
+        assert!(update.new_lines[0].starts_with('<'));
+        assert_eq!(events::read_parsed_events(path_str, 0).unwrap().len(), 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_patterns_reported_once_across_polls() {
+        let path = write_temp_log("seen_patterns", "<2024-01-01T12:00:00.000Z> [Notice] <SystemQuit> quit [Team_Core]\n");
+        let path_str = path.to_str().unwrap();
+
+        let update = scan_log_update(path_str, 0, false, true, None, None, None, None).unwrap();
+        assert_eq!(update.patterns.len(), 1);
+
+        std::fs::write(
+            &path,
+            "<2024-01-01T12:00:00.000Z> [Notice] <SystemQuit> quit [Team_Core]\n\
+             <2024-01-01T12:00:01.000Z> [Notice] <SystemQuit> again [Team_Core]\n\
+             <2024-01-01T12:00:02.000Z> [Error] <Actor Death> new pattern\n",
+        )
+        .unwrap();
+        let update = scan_log_update(path_str, 0, false, true, None, None, None, None).unwrap();
+        assert_eq!(update.patterns.len(), 1);
+        assert_eq!(update.patterns[0].severity.as_deref(), Some("Error"));
+        assert_eq!(update.patterns[0].first_seen_ms, Some(1_704_110_402_000));
+
+        clear_pattern_cache(path_str);
+        let update = scan_log_update(path_str, 0, false, true, None, None, None, None).unwrap();
+        assert_eq!(update.patterns.len(), 2);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_partial_last_line_is_not_consumed() {
+        let complete = "<2024-01-01T12:00:00.000Z> <SystemQuit> first\n";
+        let path = write_temp_log("partial", &format!("{}<2024-01-01T12:00:01.000Z> <SystemQ", complete));
+        let path_str = path.to_str().unwrap();
+
+        let update = scan_log_update(path_str, 0, false, false, None, None, None, None).unwrap();
+        assert_eq!(update.line_count, 2);
+        assert_eq!(update.committed_line_count, 1);
+        assert_eq!(update.new_lines.len(), 1);
+
+        let update = read_log_update_at(path_str, 0, false, false, None, None, None).unwrap();
+        assert_eq!(update.end_offset, complete.len() as u64);
+
+        // Once the write completes the line is read whole from the committed position
+        std::fs::write(&path, format!("{}<2024-01-01T12:00:01.000Z> <SystemQuit> second\n", complete)).unwrap();
+        let update = scan_log_update(path_str, 1, false, false, None, None, None, None).unwrap();
+        assert_eq!(update.new_lines, vec!["<2024-01-01T12:00:01.000Z> <SystemQuit> second"]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_log_update_filters_event_types() {
+        let content = "<2024-01-01T12:00:00.000Z> <Actor Death> CActor::Kill: 'A' [1]\n\
+                       <2024-01-01T12:00:01.000Z> <SystemQuit> quit\n\
+                       <2024-01-01T12:00:02.000Z> <FatalCollision> collision\n";
+        let path = write_temp_log("event_types", content);
+        let path_str = path.to_str().unwrap();
+
+        let kill_feed = Some(vec!["actor_death".to_string(), "fatal_collision".to_string()]);
+        let update = scan_log_update(path_str, 0, false, false, None, kill_feed, None, None).unwrap();
+        assert_eq!(update.new_lines.len(), 2);
+        assert!(update.new_lines.iter().all(|line| !line.contains("<SystemQuit>")));
+        // Line count is unaffected by the filter
+        assert_eq!(update.line_count, 3);
+
+        // Empty means all
+        let update = scan_log_update(path_str, 0, false, false, None, Some(Vec::new()), None, None).unwrap();
+        assert_eq!(update.new_lines.len(), 3);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    // ========================================================================
+    // parallel scan tests
+    // ========================================================================
+
+    #[test]
+    fn test_scan_parallel_matches_sequential_scan() {
+        let mut content = String::new();
+        for i in 0..300 {
+            content.push_str(&format!("<2024-01-01T12:00:00.000Z> [Notice] <SystemQuit> quit {} [Team_Core][Sub{}]\n", i, i % 7));
+            if i == 150 {
+                content.push_str("<2024-01-01T12:00:00.000Z> AccountLoginCharacterStatus_Character - name Late-Pilot - state 1\n");
+            }
+            content.push_str(&format!("<2024-01-01T12:00:00.000Z> [Trace] noise {}\n", i));
+        }
+        let path = write_temp_log("parallel", &content);
+        let path_str = path.to_str().unwrap();
+        let len = content.len() as u64;
+
+        let sequential = scan_log_update(path_str, 0, true, true, None, None, None, None).unwrap();
+        let cancellation = ReadCancellation::new(None);
+        let new_scanner = || LogScanner::new(0, true, true);
+        let (scanner, offset) = scan_parallel(path_str, len, 5, &new_scanner, &cancellation, None).unwrap();
+        assert_eq!(offset, len);
+        let parallel = scanner.finish(offset);
+
+        assert_eq!(parallel.line_count, sequential.line_count);
+        assert_eq!(parallel.new_lines, sequential.new_lines);
+        assert_eq!(parallel.line_numbers, sequential.line_numbers);
+        assert_eq!(sequential.line_numbers[..3], [0, 2, 4]);
+        assert_eq!(parallel.player_name, Some("Late-Pilot".to_string()));
+        let signatures = |update: &LogUpdate| update.patterns.iter().map(|p| p.signature.clone()).collect::<Vec<_>>();
+        assert_eq!(signatures(&parallel), signatures(&sequential));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_scan_parallel_stops_before_trailing_partial_line() {
+        let content: String = (0..40).map(|i| format!("<2024-01-01T12:00:00.000Z> <SystemQuit> quit {}\n", i)).collect();
+        let content = format!("{}<2024-01-01T12:00:00.000Z> <SystemQuit> still being writ", content);
+        let path = write_temp_log("parallel_partial", &content);
+        let path_str = path.to_str().unwrap();
+        let len = content.len() as u64;
+
+        let sequential = scan_log_update(path_str, 0, false, false, None, None, None, None).unwrap();
+        let cancellation = ReadCancellation::new(None);
+        let new_scanner = || LogScanner::new(0, false, false);
+        let (scanner, offset) = scan_parallel(path_str, len, 4, &new_scanner, &cancellation, None).unwrap();
+        let parallel = scanner.finish(offset);
+
+        assert_eq!(parallel.end_offset, content.rfind('\n').unwrap() as u64 + 1);
+        assert_eq!(parallel.end_offset, sequential.end_offset);
+        assert_eq!(parallel.committed_line_count, 40);
+        assert_eq!(parallel.line_count, sequential.line_count);
+        assert_eq!(parallel.new_lines.len(), 40);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_scan_parallel_cancelled_reports_only_what_was_read() {
+        let content = "<2024-01-01T12:00:00.000Z> <SystemQuit> quit\n".repeat(40);
+        let path = write_temp_log("parallel_cancelled", &content);
+        let path_str = path.to_str().unwrap();
+
+        let token = format!("parallel-cancel-{}", std::process::id());
+        cancel_read(token.clone());
+        let cancellation = ReadCancellation::new(Some(token));
+        let new_scanner = || LogScanner::new(0, false, false);
+        let (scanner, offset) =
+            scan_parallel(path_str, content.len() as u64, 4, &new_scanner, &cancellation, None).unwrap();
+        let update = scanner.finish(offset);
+
+        // Every chunk stopped before its first line; none of them may be counted
+        assert!(update.cancelled);
+        assert_eq!(update.end_offset, 0);
+        assert_eq!(update.line_count, 0);
+        assert!(update.new_lines.is_empty());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_chunk_boundaries_align_to_lines() {
+        let content = "aaaa\nbbbb\ncccc\ndddd\n";
+        let path = write_temp_log("boundaries", content);
+        let mut file = File::open(&path).unwrap();
+
+        let boundaries = chunk_boundaries(&mut file, content.len() as u64, 3).unwrap();
+        assert_eq!(boundaries, vec![0, 10, 15, 20]);
+        for boundary in &boundaries[1..boundaries.len() - 1] {
+            assert_eq!(content.as_bytes()[*boundary as usize - 1], b'\n');
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    // ========================================================================
+    // scan progress tests
+    // ========================================================================
+
+    #[test]
+    fn test_progress_reports_from_read_from() {
+        let content = "line\n".repeat(PROGRESS_INTERVAL_LINES * 2 + 10);
+        let reports = Mutex::new(Vec::new());
+        let report = |progress: ScanProgress| reports.lock().unwrap().push(progress);
+        let progress = ProgressReporter::new("Game.log", content.len() as u64, &report);
+
+        let mut scanner = LogScanner::new(0, false, false);
+        let cancellation = ReadCancellation::new(None);
+        scanner
+            .read_from(&mut content.as_bytes(), 0, &cancellation, Some(&progress))
+            .unwrap();
+
+        let reports = reports.into_inner().unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].lines_scanned, PROGRESS_INTERVAL_LINES);
+        assert_eq!(reports[0].total_estimate, PROGRESS_INTERVAL_LINES * 2 + 10);
+        assert_eq!(reports[1].percent, 99);
+    }
+
+    // ========================================================================
+    // read_last_lines tests
+    // ========================================================================
+
+    #[test]
+    fn test_tail_lines_across_chunks() {
+        let content: String = (0..50).map(|i| format!("line number {:02}\r\n", i)).collect();
+        let path = write_temp_log("tail_chunks", &content);
+        let mut file = File::open(&path).unwrap();
+
+        // Tiny chunks force several backward reads and lines split across chunks
+        let lines = tail_lines(&mut file, 3, 7).unwrap();
+        assert_eq!(lines, vec!["line number 47", "line number 48", "line number 49"]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_last_lines_shorter_file_and_no_trailing_newline() {
+        let path = write_temp_log("tail_short", "first\nsecond\nthird");
+        let path_str = path.to_str().unwrap();
+
+        assert_eq!(read_last_lines(path_str, 10).unwrap(), vec!["first", "second", "third"]);
+        assert_eq!(read_last_lines(path_str, 1).unwrap(), vec!["third"]);
+        assert!(read_last_lines(path_str, 0).unwrap().is_empty());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    // ========================================================================
+    // gzip archive tests
+    // ========================================================================
+
+    #[test]
+    fn test_gzipped_log_reads_like_plain_text() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let content = "<2024-01-01T12:00:00.000Z> [Notice] <AccountLoginCharacterStatus_Character> Character: ... - name Archived-Pilot - ...\n\
+                       <2024-01-01T12:00:01.000Z> [Notice] <Actor Death> CActor::Kill: 'A' [1] in zone 'z' killed by 'B' [2]\n\
+                       <2024-01-01T12:00:02.000Z> [Trace] plain line\n";
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content.as_bytes()).unwrap();
+
+        let path = std::env::temp_dir().join(format!("picologs_test_archive_{}.log.gz", std::process::id()));
+        std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+        let path_str = path.to_str().unwrap();
+
+        let metadata = get_log_metadata(path_str).unwrap();
+        assert_eq!(metadata.line_count, 3);
+        // Size is the on-disk (compressed) size
+        assert_eq!(metadata.size_bytes, std::fs::metadata(&path).unwrap().len());
+        assert!(metadata.modified_ms.is_some_and(|ms| ms > 0));
+        assert_eq!(metadata.player_name, Some("Archived-Pilot".to_string()));
+        assert_eq!(get_line_count(path_str).unwrap(), 3);
+
+        let update = scan_log_update(path_str, 0, true, true, None, None, None, None).unwrap();
+        assert_eq!(update.new_lines.len(), 2);
+        assert_eq!(update.patterns.len(), 3);
+
+        // Byte offsets refer to the decompressed stream
+        let first_line_len = content.find('\n').unwrap() as u64 + 1;
+        let update = read_log_update_at(path_str, first_line_len, false, false, None, None, None).unwrap();
+        assert_eq!(update.line_count, 2);
+        assert_eq!(update.end_offset, content.len() as u64);
+
+        // Archives cannot be tailed backward but still yield the last lines
+        let last = read_last_lines(path_str, 1).unwrap();
+        assert_eq!(last, vec!["<2024-01-01T12:00:02.000Z> [Trace] plain line"]);
+
+        std::fs::remove_file(path).unwrap();
     }
 
     // ========================================================================
@@ -882,4 +3342,121 @@ mod tests {
             .collect();
         assert!(!combat_markers.is_empty(), "Should have combat markers");
     }
+
+    #[test]
+    fn test_read_byte_range_trims_to_whole_lines() {
+        let path = write_temp_log("byte_range", "aaa\nbbb\nccc\nddd");
+        let path_str = path.to_str().unwrap();
+
+        // Starts inside "aaa", ends just before the newline after "bbb"
+        assert_eq!(read_byte_range(path_str, 1, 6).unwrap(), "bbb");
+        // Ends inside "ccc"
+        assert_eq!(read_byte_range(path_str, 4, 6).unwrap(), "bbb\n");
+        assert_eq!(read_byte_range(path_str, 0, 4).unwrap(), "aaa\n");
+        // Clamped to the end of the file, whose last line is unterminated
+        assert_eq!(read_byte_range(path_str, 8, u64::MAX).unwrap(), "ccc\nddd");
+        assert_eq!(read_byte_range(path_str, 100, 10).unwrap(), "");
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    /// Reader that fails with `kind` after every chunk it hands out, `failures_left` times
+    struct FlakyReader {
+        content: std::io::Cursor<Vec<u8>>,
+        failures_left: usize,
+        fail_next: bool,
+        kind: std::io::ErrorKind,
+    }
+
+    impl Read for FlakyReader {
+        fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+            if self.failures_left > 0 && self.fail_next {
+                self.failures_left -= 1;
+                self.fail_next = false;
+                return Err(std::io::Error::from(self.kind));
+            }
+            self.fail_next = true;
+            // Hand out a few bytes at a time so failures land mid-line
+            let len = out.len().min(4);
+            self.content.read(&mut out[..len])
+        }
+    }
+
+    fn flaky(content: &str, failures: usize, kind: std::io::ErrorKind) -> BufReader<FlakyReader> {
+        BufReader::with_capacity(
+            4,
+            FlakyReader {
+                content: std::io::Cursor::new(content.as_bytes().to_vec()),
+                failures_left: failures,
+                fail_next: false,
+                kind,
+            },
+        )
+    }
+
+    #[test]
+    fn test_read_raw_line_retries_transient_errors() {
+        let delays = [Duration::ZERO; 3];
+        let mut buf = Vec::new();
+
+        let mut reader = flaky("<Actor Death> one\nnext\n", 3, std::io::ErrorKind::ResourceBusy);
+        assert_eq!(read_raw_line_retrying(&mut reader, &mut buf, &delays), Ok(18));
+        assert_eq!(buf, b"<Actor Death> one");
+
+        // Out of retries
+        let mut reader = flaky("a much longer line\n", 4, std::io::ErrorKind::ResourceBusy);
+        assert!(matches!(
+            read_raw_line_retrying(&mut reader, &mut buf, &delays),
+            Err(LogError::Io(_))
+        ));
+
+        // Not retryable: fails on the first attempt
+        let mut reader = flaky("line\n", 1, std::io::ErrorKind::PermissionDenied);
+        assert_eq!(
+            read_raw_line_retrying(&mut reader, &mut buf, &delays),
+            Err(LogError::PermissionDenied)
+        );
+    }
+
+    fn utf16le(text: &str, bom: bool) -> Vec<u8> {
+        let bom = if bom { vec![0xFF, 0xFE] } else { Vec::new() };
+        bom.into_iter().chain(text.encode_utf16().flat_map(u16::to_le_bytes)).collect()
+    }
+
+    #[test]
+    fn test_utf16_encoding_detection() {
+        let text = "<2024-01-01T12:00:00.000Z> [Notice] <Actor Death> umlaut ä\n";
+        assert_eq!(utf16_encoding(&utf16le(text, true)), Some(encoding_rs::UTF_16LE));
+        assert_eq!(utf16_encoding(&utf16le(text, false)), Some(encoding_rs::UTF_16LE));
+        let be: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        assert_eq!(utf16_encoding(&be), Some(encoding_rs::UTF_16BE));
+        assert_eq!(utf16_encoding(text.as_bytes()), None);
+        assert_eq!(utf16_encoding(b"\xEF\xBB\xBFplain UTF-8 with a BOM"), None);
+    }
+
+    #[test]
+    fn test_reads_utf16_logs_as_utf8() {
+        let content = "<2024-01-01T12:00:00.000Z> <Actor Death> Pilot ä\r\nplain line\n<2024-01-01T12:00:01.000Z> <SystemQuit> bye\n";
+        let path = write_temp_log("utf16", "");
+        std::fs::write(&path, utf16le(content, true)).unwrap();
+        let path_str = path.to_str().unwrap();
+
+        let update = scan_log_update(path_str, 0, false, false, None, None, None, None).unwrap();
+        assert_eq!(
+            update.new_lines,
+            vec![
+                "<2024-01-01T12:00:00.000Z> <Actor Death> Pilot ä".to_string(),
+                "<2024-01-01T12:00:01.000Z> <SystemQuit> bye".to_string(),
+            ]
+        );
+        // Offsets are into the UTF-8 text, and resuming from one skips by decoding
+        assert_eq!(update.end_offset, content.len() as u64);
+        let second_line = content.find("plain").unwrap() as u64;
+        let resumed = read_log_update_at(path_str, second_line, false, false, None, None, None).unwrap();
+        assert_eq!(resumed.new_lines, vec!["<2024-01-01T12:00:01.000Z> <SystemQuit> bye".to_string()]);
+
+        assert_eq!(read_last_lines(path_str, 1).unwrap(), vec!["<2024-01-01T12:00:01.000Z> <SystemQuit> bye".to_string()]);
+
+        std::fs::remove_file(path).unwrap();
+    }
 }
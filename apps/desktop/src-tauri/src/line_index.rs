@@ -0,0 +1,138 @@
+//! Line number → byte offset lookups for jumping the raw-log viewer to an event
+//!
+//! The first lookup scans the file once and caches where every line starts; later
+//! lookups are a vector index, and a grown log is only scanned from where the
+//! cached index ended.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Read, Seek, SeekFrom};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+use crate::error::LogError;
+use crate::{log_reader, needs_decoding, open_log_file, read_log_head};
+
+/// Start offsets of the complete lines seen so far in one file
+struct LineIndex {
+    /// File head when the index was built, to notice a rotated log
+    head: Vec<u8>,
+    /// `offsets[n]` is where line `n` begins
+    offsets: Vec<u64>,
+    /// End of the last complete (newline-terminated) line indexed
+    indexed_bytes: u64,
+}
+
+impl LineIndex {
+    fn new(head: Vec<u8>) -> Self {
+        Self {
+            head,
+            offsets: Vec::new(),
+            indexed_bytes: 0,
+        }
+    }
+
+    /// Index complete lines from `reader`, which is positioned at `indexed_bytes`.
+    /// Returns true if an unterminated line follows them.
+    fn extend<R: BufRead>(&mut self, reader: &mut R) -> Result<bool, LogError> {
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            let bytes_read = reader.read_until(b'\n', &mut buf)?;
+            if bytes_read == 0 {
+                return Ok(false);
+            }
+            if buf.last() != Some(&b'\n') {
+                return Ok(true);
+            }
+            self.offsets.push(self.indexed_bytes);
+            self.indexed_bytes += bytes_read as u64;
+        }
+    }
+}
+
+// Cached indexes keyed by the path passed to line_offset
+lazy_static! {
+    static ref LINE_INDEXES: Mutex<HashMap<String, LineIndex>> = Mutex::new(HashMap::new());
+}
+
+/// Byte offset where line `line` (0-based) of `path` begins, or None if the file has
+/// fewer lines. For gzip archives and UTF-16 logs the offset is into the decoded text, matching
+/// the offsets read_log_update_at takes.
+#[tauri::command]
+pub fn line_offset(path: &str, line: usize) -> Result<Option<u64>, LogError> {
+    let mut file = open_log_file(path)?;
+    let file_len = file.metadata()?.len();
+    let head = read_log_head(&mut file)?;
+    let decoded = needs_decoding(path, &head);
+
+    let mut indexes = LINE_INDEXES.lock().unwrap();
+    let index = indexes
+        .entry(path.to_string())
+        .or_insert_with(|| LineIndex::new(head.clone()));
+
+    // Only the overlapping prefix is compared: a log still filling its first bytes
+    // grows its head without being a different file
+    let common = index.head.len().min(head.len());
+    let rotated = index.head[..common] != head[..common] || (!decoded && file_len < index.indexed_bytes);
+    if rotated {
+        *index = LineIndex::new(head.clone());
+    }
+
+    if let Some(&offset) = index.offsets.get(line) {
+        return Ok(Some(offset));
+    }
+
+    // Extend the index over whatever was appended since it was built
+    let trailing_partial = if decoded {
+        // Decoded offsets can't be seeked to, so skip ahead by decoding
+        let mut reader = log_reader(path, &head, file);
+        std::io::copy(&mut reader.by_ref().take(index.indexed_bytes), &mut std::io::sink())?;
+        index.extend(&mut reader)?
+    } else {
+        file.seek(SeekFrom::Start(index.indexed_bytes))?;
+        index.extend(&mut log_reader(path, &head, file))?
+    };
+    index.head = head;
+
+    Ok(match index.offsets.get(line) {
+        Some(&offset) => Some(offset),
+        // A line SC is still writing already has a start
+        None if trailing_partial && line == index.offsets.len() => Some(index.indexed_bytes),
+        None => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_offset_grows_with_file() {
+        let path = crate::tests::write_temp_log("line_offset", "first\nsecond line\r\nthird");
+        let path_str = path.to_str().unwrap();
+
+        assert_eq!(line_offset(path_str, 0).unwrap(), Some(0));
+        assert_eq!(line_offset(path_str, 1).unwrap(), Some(6));
+        assert_eq!(line_offset(path_str, 2).unwrap(), Some(19));
+        assert_eq!(line_offset(path_str, 3).unwrap(), None);
+
+        std::fs::write(&path, "first\nsecond line\r\nthird\nfourth\n").unwrap();
+        assert_eq!(line_offset(path_str, 3).unwrap(), Some(25));
+        assert_eq!(line_offset(path_str, 4).unwrap(), None);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_line_offset_rebuilds_after_rotation() {
+        let path = crate::tests::write_temp_log("line_offset_rotated", "old session line\nmore\n");
+        let path_str = path.to_str().unwrap();
+        assert_eq!(line_offset(path_str, 1).unwrap(), Some(17));
+
+        std::fs::write(&path, "new\nlog\n").unwrap();
+        assert_eq!(line_offset(path_str, 1).unwrap(), Some(4));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,444 @@
+//! User-defined marker rules (literal or regex) with their own event types
+//!
+//! Complements the compiled EVENT_MARKERS: rules are compiled once when set (or
+//! restored at startup) and consulted only for lines the literal markers missed.
+//! Field extractors work the same way one step later: a regex per event type whose
+//! groups become attributes of parsed events, so new events can be given fields
+//! from the pattern-discovery screen without a code change.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+use regex::{Regex, RegexBuilder, RegexSet};
+
+/// A marker rule as configured by the frontend
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct MarkerRule {
+    pub pattern: String,
+    #[serde(default)]
+    pub is_regex: bool,
+    pub event_type: String,
+}
+
+/// Rules split into the literal fast path and a single RegexSet for the regex rules
+pub struct CompiledRules {
+    literals: Vec<MarkerRule>,
+    regex_set: RegexSet,
+    regexes: Vec<(Regex, String)>,
+}
+
+/// Event type and named captures of the rule a line matched
+#[derive(Debug)]
+pub struct RuleMatch {
+    pub event_type: String,
+    pub captures: HashMap<String, String>,
+}
+
+lazy_static! {
+    static ref MARKER_RULES: RwLock<CompiledRules> = RwLock::new(CompiledRules::empty());
+}
+
+/// Store key the rule list is persisted under (in crate::SETTINGS_STORE)
+const MARKER_RULES_KEY: &str = "markerRules";
+
+/// Longest pattern accepted for a user regex
+const MAX_USER_REGEX_LEN: usize = 1024;
+
+/// Compiled size cap for a user regex. Matching is linear-time whatever the pattern,
+/// but large counted repetitions (`(a{100}){100}`) blow up the compiled program.
+const USER_REGEX_SIZE_LIMIT: usize = 256 * 1024;
+
+/// Compile a regex entered by the user, within the length and size limits
+fn compile_user_regex(pattern: &str) -> Result<Regex, String> {
+    if pattern.len() > MAX_USER_REGEX_LEN {
+        return Err(format!("Regex is longer than {} characters", MAX_USER_REGEX_LEN));
+    }
+    RegexBuilder::new(pattern)
+        .size_limit(USER_REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// Result of validate_regex
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct RegexValidation {
+    pub valid: bool,
+    pub error: Option<String>,
+    /// Names of the named groups, in order
+    pub capture_names: Vec<String>,
+}
+
+/// Check a regex from a settings form before it is saved as a marker rule or field
+/// extractor, with the same limits applied when saving
+#[tauri::command]
+pub fn validate_regex(pattern: String) -> RegexValidation {
+    match compile_user_regex(&pattern) {
+        Ok(re) => RegexValidation {
+            valid: true,
+            error: None,
+            capture_names: re.capture_names().flatten().map(str::to_string).collect(),
+        },
+        Err(error) => RegexValidation {
+            valid: false,
+            error: Some(error),
+            capture_names: Vec::new(),
+        },
+    }
+}
+
+impl CompiledRules {
+    fn empty() -> Self {
+        Self {
+            literals: Vec::new(),
+            regex_set: RegexSet::empty(),
+            regexes: Vec::new(),
+        }
+    }
+
+    /// Compile a rule list, failing on the first invalid regex
+    pub fn compile(rules: Vec<MarkerRule>) -> Result<Self, String> {
+        let mut literals = Vec::new();
+        let mut regexes = Vec::new();
+
+        for rule in rules {
+            if rule.pattern.is_empty() {
+                continue;
+            }
+            if rule.is_regex {
+                let re = compile_user_regex(&rule.pattern)
+                    .map_err(|e| format!("Invalid marker regex '{}': {}", rule.pattern, e))?;
+                regexes.push((re, rule.event_type));
+            } else {
+                literals.push(rule);
+            }
+        }
+
+        let regex_set = RegexSet::new(regexes.iter().map(|(re, _)| re.as_str()))
+            .map_err(|e| format!("Invalid marker regex: {}", e))?;
+
+        Ok(Self {
+            literals,
+            regex_set,
+            regexes,
+        })
+    }
+
+    /// Whether any rule matches the line (literals first, then one RegexSet pass)
+    pub fn is_match(&self, line: &str) -> bool {
+        self.literals.iter().any(|rule| line.contains(rule.pattern.as_str()))
+            || (!self.regexes.is_empty() && self.regex_set.is_match(line))
+    }
+
+    /// First rule matching the line, with named regex groups as captures
+    pub fn find(&self, line: &str) -> Option<RuleMatch> {
+        if let Some(rule) = self.literals.iter().find(|rule| line.contains(rule.pattern.as_str())) {
+            return Some(RuleMatch {
+                event_type: rule.event_type.clone(),
+                captures: HashMap::new(),
+            });
+        }
+
+        if self.regexes.is_empty() {
+            return None;
+        }
+        let index = self.regex_set.matches(line).into_iter().next()?;
+        let (re, event_type) = &self.regexes[index];
+        let caps = re.captures(line)?;
+
+        let captures = re
+            .capture_names()
+            .flatten()
+            .filter_map(|name| caps.name(name).map(|m| (name.to_string(), m.as_str().to_string())))
+            .collect();
+
+        Some(RuleMatch {
+            event_type: event_type.clone(),
+            captures,
+        })
+    }
+}
+
+/// Whether a line matches any active marker rule
+pub fn matches_rule(line: &str) -> bool {
+    MARKER_RULES.read().unwrap().is_match(line)
+}
+
+/// Active marker rule matching a line, if any
+pub fn find_rule(line: &str) -> Option<RuleMatch> {
+    MARKER_RULES.read().unwrap().find(line)
+}
+
+/// The active rules, literals first. Literals are always tried before regexes, so
+/// this order matches the compiled rules exactly.
+pub fn active_rules() -> Vec<MarkerRule> {
+    let rules = MARKER_RULES.read().unwrap();
+    let regex_rules = rules.regexes.iter().map(|(re, event_type)| MarkerRule {
+        pattern: re.as_str().to_string(),
+        is_regex: true,
+        event_type: event_type.clone(),
+    });
+    rules.literals.iter().cloned().chain(regex_rules).collect()
+}
+
+/// Replace the active marker rules. Regexes are validated and compiled up front,
+/// so an invalid pattern leaves the previous rules in place. Persisted across restarts.
+#[tauri::command]
+pub fn set_marker_rules(rules: Vec<MarkerRule>, app: tauri::AppHandle) -> Result<(), String> {
+    use tauri_plugin_store::StoreExt;
+
+    let compiled = CompiledRules::compile(rules.clone())?;
+
+    let store = app.store(crate::SETTINGS_STORE).map_err(|e| e.to_string())?;
+    store.set(MARKER_RULES_KEY, serde_json::json!(rules));
+    store.save().map_err(|e| e.to_string())?;
+
+    *MARKER_RULES.write().unwrap() = compiled;
+    Ok(())
+}
+
+/// A user-defined parser: on lines of `event_type` matching `regex`, capture group N
+/// becomes attribute `field_names[N - 1]`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct FieldExtractor {
+    pub event_type: String,
+    pub regex: String,
+    pub field_names: Vec<String>,
+}
+
+lazy_static! {
+    static ref FIELD_EXTRACTORS: RwLock<Vec<(FieldExtractor, Regex)>> = RwLock::new(Vec::new());
+}
+
+/// Store key the field extractors are persisted under (in crate::SETTINGS_STORE)
+const FIELD_EXTRACTORS_KEY: &str = "fieldExtractors";
+
+/// Validate an extractor: a compiling regex with a group for every field name
+fn compile_extractor(extractor: FieldExtractor) -> Result<(FieldExtractor, Regex), String> {
+    if extractor.event_type.is_empty() {
+        return Err("Field extractor needs an event type".to_string());
+    }
+    if extractor.field_names.is_empty() || extractor.field_names.iter().any(|name| name.is_empty()) {
+        return Err("Field extractor needs non-empty field names".to_string());
+    }
+    let re = compile_user_regex(&extractor.regex)
+        .map_err(|e| format!("Invalid field extractor regex '{}': {}", extractor.regex, e))?;
+    let groups = re.captures_len() - 1;
+    if groups < extractor.field_names.len() {
+        return Err(format!(
+            "Field extractor regex has {} groups for {} field names",
+            groups,
+            extractor.field_names.len()
+        ));
+    }
+    Ok((extractor, re))
+}
+
+/// Fill `attributes` from the extractors for `event_type` that match the line.
+/// Fields already set (by built-in parsers or an earlier extractor) are kept.
+fn apply_extractors(
+    extractors: &[(FieldExtractor, Regex)],
+    event_type: &str,
+    line: &str,
+    attributes: &mut HashMap<String, String>,
+) {
+    for (extractor, re) in extractors.iter().filter(|(e, _)| e.event_type == event_type) {
+        let Some(caps) = re.captures(line) else {
+            continue;
+        };
+        for (i, name) in extractor.field_names.iter().enumerate() {
+            if let Some(value) = caps.get(i + 1) {
+                attributes
+                    .entry(name.clone())
+                    .or_insert_with(|| value.as_str().to_string());
+            }
+        }
+    }
+}
+
+/// Add the fields of the active extractors to a parsed event's attributes
+pub fn extract_fields(event_type: &str, line: &str, attributes: &mut HashMap<String, String>) {
+    apply_extractors(&FIELD_EXTRACTORS.read().unwrap(), event_type, line, attributes);
+}
+
+/// Register a field extractor for an event type, replacing one with the same event
+/// type and regex. The regex is validated first. Persisted across restarts.
+#[tauri::command]
+pub fn add_field_extractor(
+    event_type: String,
+    regex: String,
+    field_names: Vec<String>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    use tauri_plugin_store::StoreExt;
+
+    let (extractor, re) = compile_extractor(FieldExtractor {
+        event_type,
+        regex,
+        field_names,
+    })?;
+
+    let mut extractors = FIELD_EXTRACTORS.write().unwrap();
+    let mut updated: Vec<FieldExtractor> = extractors
+        .iter()
+        .map(|(e, _)| e.clone())
+        .filter(|e| e.event_type != extractor.event_type || e.regex != extractor.regex)
+        .collect();
+    updated.push(extractor.clone());
+
+    let store = app.store(crate::SETTINGS_STORE).map_err(|e| e.to_string())?;
+    store.set(FIELD_EXTRACTORS_KEY, serde_json::json!(updated));
+    store.save().map_err(|e| e.to_string())?;
+
+    extractors.retain(|(e, _)| e.event_type != extractor.event_type || e.regex != extractor.regex);
+    extractors.push((extractor, re));
+    Ok(())
+}
+
+/// Compile the field extractors persisted by add_field_extractor, skipping invalid ones
+pub fn load_field_extractors(app: &tauri::AppHandle) {
+    use tauri_plugin_store::StoreExt;
+
+    let Ok(store) = app.store(crate::SETTINGS_STORE) else {
+        return;
+    };
+    let Some(value) = store.get(FIELD_EXTRACTORS_KEY) else {
+        return;
+    };
+    let Ok(stored) = serde_json::from_value::<Vec<FieldExtractor>>(value) else {
+        eprintln!("[Rust] Ignoring unreadable stored field extractors");
+        return;
+    };
+
+    let compiled = stored
+        .into_iter()
+        .filter_map(|extractor| {
+            compile_extractor(extractor)
+                .map_err(|e| eprintln!("[Rust] Ignoring invalid stored field extractor: {}", e))
+                .ok()
+        })
+        .collect();
+    *FIELD_EXTRACTORS.write().unwrap() = compiled;
+}
+
+/// Compile the marker rules persisted by set_marker_rules
+pub fn load_marker_rules(app: &tauri::AppHandle) {
+    use tauri_plugin_store::StoreExt;
+
+    let Ok(store) = app.store(crate::SETTINGS_STORE) else {
+        return;
+    };
+    let Some(value) = store.get(MARKER_RULES_KEY) else {
+        return;
+    };
+
+    let compiled = serde_json::from_value::<Vec<MarkerRule>>(value)
+        .map_err(|e| e.to_string())
+        .and_then(CompiledRules::compile);
+    match compiled {
+        Ok(compiled) => *MARKER_RULES.write().unwrap() = compiled,
+        Err(e) => eprintln!("[Rust] Ignoring invalid stored marker rules: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, is_regex: bool, event_type: &str) -> MarkerRule {
+        MarkerRule {
+            pattern: pattern.to_string(),
+            is_regex,
+            event_type: event_type.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_regex_rule_captures_named_groups() {
+        let rules = CompiledRules::compile(vec![rule(
+            r"<Quantum Drive Arrived.*?> (?P<ship>[A-Za-z_]+)_\d+\[",
+            true,
+            "quantum_destination",
+        )])
+        .unwrap();
+
+        let line = "<2025-11-02T07:47:10.855Z> <Quantum Drive Arrived - Arrived at Final Destination> AEGS_Gladius_123[123]|Component";
+        let found = rules.find(line).unwrap();
+
+        assert_eq!(found.event_type, "quantum_destination");
+        assert_eq!(found.captures.get("ship"), Some(&"AEGS_Gladius".to_string()));
+        assert!(!rules.is_match("<2025-11-02T07:47:10.855Z> <Quantum Drive Arrived> no ship here"));
+    }
+
+    #[test]
+    fn test_literal_rule_wins_over_regex() {
+        let rules = CompiledRules::compile(vec![
+            rule("<NewEvent>", false, "new_event"),
+            rule(r"New.*", true, "regex_event"),
+        ])
+        .unwrap();
+
+        let found = rules.find("<NewEvent> something").unwrap();
+        assert_eq!(found.event_type, "new_event");
+        assert!(found.captures.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_regex_rejected() {
+        let result = CompiledRules::compile(vec![rule("(unclosed", true, "broken")]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_field_extractor_fills_attributes() {
+        let extractors = vec![compile_extractor(FieldExtractor {
+            event_type: "custom".to_string(),
+            regex: r"<Hangar Request> pad (\w+) for (\w+)".to_string(),
+            field_names: vec!["pad".to_string(), "ship".to_string()],
+        })
+        .unwrap()];
+
+        let mut attributes = HashMap::from([("ship".to_string(), "from a parser".to_string())]);
+        apply_extractors(&extractors, "custom", "<Hangar Request> pad 04 for Cutlass", &mut attributes);
+        assert_eq!(attributes.get("pad"), Some(&"04".to_string()));
+        assert_eq!(attributes.get("ship"), Some(&"from a parser".to_string()));
+
+        // Other event types are untouched
+        let mut attributes = HashMap::new();
+        apply_extractors(&extractors, "actor_death", "<Hangar Request> pad 04 for Cutlass", &mut attributes);
+        assert!(attributes.is_empty());
+
+        let too_few_groups = compile_extractor(FieldExtractor {
+            event_type: "custom".to_string(),
+            regex: r"pad (\w+)".to_string(),
+            field_names: vec!["pad".to_string(), "ship".to_string()],
+        });
+        assert!(too_few_groups.is_err());
+    }
+
+    #[test]
+    fn test_empty_rules_match_nothing() {
+        let rules = CompiledRules::empty();
+        assert!(!rules.is_match("<Actor Death> anything"));
+        assert!(rules.find("<Actor Death> anything").is_none());
+    }
+
+    #[test]
+    fn test_validate_regex() {
+        assert_eq!(
+            validate_regex(r"killed by '(?P<killer>[^']+)' using (\w+)".to_string()),
+            RegexValidation {
+                valid: true,
+                error: None,
+                capture_names: vec!["killer".to_string()],
+            }
+        );
+
+        let unclosed = validate_regex("(unclosed".to_string());
+        assert!(!unclosed.valid);
+        assert!(unclosed.error.is_some());
+
+        assert!(!validate_regex("a".repeat(MAX_USER_REGEX_LEN + 1)).valid);
+        assert!(!validate_regex(r"((\w{100}){100}){100}".to_string()).valid);
+    }
+}
@@ -0,0 +1,1085 @@
+//! Field extraction for specific Star Citizen events
+//!
+//! Each parser takes a full log line and returns None when the line is not the event
+//! it handles or the fields it needs are missing (format drift after a patch).
+
+use std::collections::VecDeque;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // <Actor Death> CActor::Kill: 'victim' [id] in zone 'zone' killed by 'killer' [id] using 'weapon' [Class ...] with damage type 'type'
+    static ref DEATH_VICTIM_RE: Regex = Regex::new(r"CActor::Kill:\s+'([^']+)'\s+\[(\d+)\]").unwrap();
+    static ref DEATH_KILLER_RE: Regex = Regex::new(r"killed by\s+'([^']+)'\s+\[(\d+)\]").unwrap();
+    static ref DEATH_ZONE_RE: Regex = Regex::new(r"in zone\s+'([^']+)'").unwrap();
+    static ref DEATH_WEAPON_RE: Regex = Regex::new(r"using\s+'([^']+)'").unwrap();
+    static ref DEATH_DAMAGE_TYPE_RE: Regex = Regex::new(r"with damage type\s+'([^']+)'").unwrap();
+
+    // <Vehicle Control Flow> ... Local client node [id] granted/releasing control token for 'vehicle' [id]
+    static ref VEHICLE_CONTROL_RE: Regex =
+        Regex::new(r"(requesting|granted|releasing) control token for\s+'([^']+)'\s+\[(\d+)\]").unwrap();
+    static ref VEHICLE_SEAT_RE: Regex = Regex::new(r"seat\s+'([^']+)'").unwrap();
+
+    // <Jump Drive Requesting State Change> Requested change from Idle to Spooling, reason: ... | System | (player: SHIP in zone ZONE)
+    static ref JUMP_DRIVE_STATE_RE: Regex =
+        Regex::new(r"Requested\s+change\s+from\s+(\w+)\s+to\s+(\w+),\s+reason:\s+([^|]+)").unwrap();
+    // <Quantum Drive Arrived - Arrived at Final Destination> ... VEHICLE_123[123]|CSCItemNavigation::OnQuantumDriveArrived|...
+    static ref QUANTUM_ARRIVAL_VEHICLE_RE: Regex = Regex::new(r"([A-Za-z_0-9]+)\[(\d+)\]\|").unwrap();
+    // <MissionShared> Received share push message: ownerId[X] - missionId[Y]
+    static ref MISSION_SHARED_RE: Regex = Regex::new(r"missionId\[([A-Za-z0-9\-]+)\]").unwrap();
+    // <ObjectiveUpserted> Received ObjectiveUpserted push message for: mission_id X - objective_id Y - state Z
+    static ref OBJECTIVE_UPSERTED_RE: Regex =
+        Regex::new(r"mission_id\s+([A-Za-z0-9\-]+)\s+-\s+objective_id\s+([A-Za-z0-9\-_]+)\s+-\s+state\s+(\w+)").unwrap();
+    // <MissionEnded> Received MissionEnded push message for: mission_id X - mission_state Y
+    static ref MISSION_ENDED_RE: Regex =
+        Regex::new(r"mission_id\s+([A-Za-z0-9\-]+)\s+-\s+mission_state\s+(\w+)").unwrap();
+    // <EndMission> Ending mission for player. MissionId[X] Player[Y] PlayerId[Z] CompletionType[T] Reason[R]
+    static ref END_MISSION_ID_RE: Regex = Regex::new(r"MissionId\[([A-Za-z0-9\-]+)\]").unwrap();
+    static ref END_MISSION_TYPE_RE: Regex = Regex::new(r"CompletionType\[([^\]]+)\]").unwrap();
+    // <CLocalMissionPhaseMarker::CreateMarker> Creating objective marker: missionId [X], generator name [G], contract [C], objectiveId [O]
+    static ref MARKER_MISSION_ID_RE: Regex = Regex::new(r"missionId\s*\[([A-Za-z0-9\-]+)\]").unwrap();
+    static ref MARKER_CONTRACT_RE: Regex = Regex::new(r"contract\s*\[([^\]]+)\]").unwrap();
+    static ref MARKER_OBJECTIVE_RE: Regex = Regex::new(r"objectiveId\s*\[([A-Za-z0-9\-_]+)\]").unwrap();
+    // x: 1.2345e+07, y: -20.5, z: 3
+    static ref COORDINATES_RE: Regex = Regex::new(
+        r"\bx:\s*([-+]?(?:\d+\.?\d*|\.\d+)(?:[eE][-+]?\d+)?),?\s*y:\s*([-+]?(?:\d+\.?\d*|\.\d+)(?:[eE][-+]?\d+)?),?\s*z:\s*([-+]?(?:\d+\.?\d*|\.\d+)(?:[eE][-+]?\d+)?)"
+    )
+    .unwrap();
+    // <EquipItem> Request[X] equip from Inventory[Y:Location:Z] Class[ItemClass] Rank[...] Port[...] ... PostAction[Wear]
+    // <AttachmentReceived> Player[X] Attachment[Y] Status[Z] Port[W]
+    static ref ITEM_CLASS_RE: Regex = Regex::new(r"Class\[([^\]]+)\]").unwrap();
+    static ref ITEM_INVENTORY_RE: Regex = Regex::new(r"Inventory\[[^:\]]*:([^:\]]+):").unwrap();
+    static ref ITEM_POST_ACTION_RE: Regex = Regex::new(r"PostAction\[([^\]]+)\]").unwrap();
+    static ref ATTACHMENT_RE: Regex = Regex::new(r"Attachment\[([^\]]+)\]").unwrap();
+    static ref ATTACHMENT_STATUS_RE: Regex = Regex::new(r"Status\[([^\]]+)\]").unwrap();
+    static ref ITEM_PORT_RE: Regex = Regex::new(r"Port\[([^\]]+)\]").unwrap();
+    static ref JUMP_DRIVE_ZONE_RE: Regex = Regex::new(r"\(\w+:\s+([A-Za-z_0-9]+)\s+in\s+zone\s+([^)]+)\)").unwrap();
+    // <CLandingArea::OnDoorOpenStateChanged> ... LandingArea_X ... Door: HangarDoor_Y ... State: Z
+    static ref LANDING_AREA_RE: Regex = Regex::new(r"\b(LandingArea_\w+)").unwrap();
+    static ref LANDING_STATE_RE: Regex = Regex::new(r"State:\s+(\w+)").unwrap();
+    // <CSCItemDockingTube::OnSetTubeState> ... OnSetTubeState to X, last tube state is Y
+    static ref DOCKING_TUBE_STATE_RE: Regex = Regex::new(r"OnSetTubeState\s+to\s+(\w+)").unwrap();
+    // <CEntityComponentShoppingProvider::SendStandardItemBuyRequest> ... itemName[X] client_price[Y] shopName[Z]
+    static ref PURCHASE_ITEM_RE: Regex = Regex::new(r"itemName\[([^\]]+)\]").unwrap();
+    static ref PURCHASE_PRICE_RE: Regex = Regex::new(r"client_price\[([^\]]+)\]").unwrap();
+    static ref PURCHASE_SHOP_RE: Regex = Regex::new(r"shopName\[([^\]]+)\]").unwrap();
+    // <CWallet::ProcessClaimToNextStep> ... entitlementURN: X [amount[Y]]
+    static ref CLAIM_URN_RE: Regex = Regex::new(r"entitlementURN:\s+([^\s,]+)").unwrap();
+    static ref CLAIM_AMOUNT_RE: Regex = Regex::new(r"(?i)\b(?:amount|price|cost)\[([^\]]+)\]").unwrap();
+    // <[STAMINA] ...> Player[X] ... Stamina: 0.42 (or Current[0.42])
+    static ref STAMINA_VALUE_RE: Regex =
+        Regex::new(r"(?i)\b(?:stamina|current|value)\s*[:=\[]\s*([-+]?(?:\d+\.?\d*|\.\d+))").unwrap();
+    // <FatalCollision> Fatal Collision occured for vehicle X [Part: Y, Pos: ..., Zone: Z, PlayerPilot: 1] after hitting entity: W [...]
+    static ref COLLISION_VEHICLE_RE: Regex = Regex::new(r"for vehicle\s+([A-Za-z_0-9]+)").unwrap();
+    static ref COLLISION_ZONE_RE: Regex = Regex::new(r"Zone:\s+([^,\]]+)").unwrap();
+    static ref COLLISION_HIT_RE: Regex = Regex::new(r"after hitting entity:\s+([^\[\]]+)").unwrap();
+    static ref COLLISION_SPEED_RE: Regex =
+        Regex::new(r"(?i)\b(?:speed|velocity)\s*[:=]?\s*([+-]?\d+(?:\.\d+)?(?:e[+-]?\d+)?)").unwrap();
+    // <Spawn Flow> Player 'X' ... lost reservation for spawnpoint Y [id] at location Z
+    static ref SPAWN_PLAYER_RE: Regex = Regex::new(r"Player\s+'([^']+)'").unwrap();
+    static ref SPAWN_POINT_RE: Regex = Regex::new(r"spawnpoint\s+([^\s\[]+)").unwrap();
+    static ref SPAWN_LOCATION_RE: Regex = Regex::new(r"at\s+location\s+(\S+)").unwrap();
+    // <CEntity::OnOwnerRemoved> ... Player[X] ... (or Entity[X] for item/vehicle ownership)
+    static ref OWNER_REMOVED_PLAYER_RE: Regex = Regex::new(r"\b(?:Player|Owner)\[([^\]]+)\]").unwrap();
+    static ref OWNER_REMOVED_ENTITY_RE: Regex = Regex::new(r"\bEntity\s*\[([^\]]+)\]").unwrap();
+    static ref OWNER_REMOVED_ZONE_RE: Regex = Regex::new(r"\bZone\[([^\]]+)\]").unwrap();
+    // Station and planet ids (RR_HUR_LEO, Stanton1_Lorville, stanton2b)
+    static ref LOCATION_ID_RE: Regex = Regex::new(r"\b(RR_[A-Z]{3}_\w+|(?i:stanton|pyro)\w*)").unwrap();
+    // <CSCLoadingPlatformManager> [LoadingPlatformManager_X] Platform state changed from A to B (or platform[X] state[B])
+    static ref PLATFORM_ID_RE: Regex =
+        Regex::new(r"\b(LoadingPlatform\w*_\w+)|(?i:\bplatform(?:_?id)?)\[([^\]]+)\]").unwrap();
+    static ref PLATFORM_STATE_RE: Regex =
+        Regex::new(r"(?i)\bstate\s*(?:changed\s+)?(?:from\s+'?\w+'?\s+)?(?:to\s*:?|:|\[)\s*'?(\w+)").unwrap();
+}
+
+/// Readable names for internal location ids, matched case-insensitively as substrings
+/// (more specific ids first, so `stanton2b` is Daymar rather than Crusader)
+const KNOWN_LOCATIONS: &[(&str, &str)] = &[
+    ("rr_hur_leo", "Everus Harbor"),
+    ("rr_cru_leo", "Seraphim Station"),
+    ("rr_arc_leo", "Baijini Point"),
+    ("rr_mic_leo", "Port Tressler"),
+    ("stanton1_lorville", "Lorville"),
+    ("stanton2_orison", "Orison"),
+    ("stanton3_area18", "Area18"),
+    ("stanton4_newbabbage", "New Babbage"),
+    ("stanton1a", "Arial"),
+    ("stanton1b", "Aberdeen"),
+    ("stanton1c", "Magda"),
+    ("stanton1d", "Ita"),
+    ("stanton2a", "Cellin"),
+    ("stanton2b", "Daymar"),
+    ("stanton2c", "Yela"),
+    ("stanton3a", "Lyria"),
+    ("stanton3b", "Wala"),
+    ("stanton4a", "Calliope"),
+    ("stanton4b", "Clio"),
+    ("stanton4c", "Euterpe"),
+    ("stanton1", "Hurston"),
+    ("stanton2", "Crusader"),
+    ("stanton3", "ArcCorp"),
+    ("stanton4", "microTech"),
+];
+
+/// Engine-added name parts that are not part of a vehicle's class
+const VEHICLE_TECHNICAL_PARTS: &[&str] = &["PU", "AI", "Al", "FF", "CRIM", "PDC"];
+
+/// Killer/victim details from an `<Actor Death>` line
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct ActorDeath {
+    /// Victim name as logged (NPC names keep their underscores)
+    pub victim: String,
+    pub victim_geid: String,
+    /// None for environmental or otherwise unattributed deaths (`'unknown' [0]`)
+    pub killer: Option<String>,
+    pub killer_geid: Option<String>,
+    pub weapon: Option<String>,
+    pub damage_type: Option<String>,
+    pub zone: Option<String>,
+    /// Killer and victim are the same entity
+    pub suicide: bool,
+}
+
+/// Parse an `<Actor Death>` line into killer, victim, weapon and damage type
+pub fn parse_actor_death(line: &str) -> Option<ActorDeath> {
+    if !line.contains("<Actor Death>") {
+        return None;
+    }
+
+    let victim = DEATH_VICTIM_RE.captures(line)?;
+    let killer = DEATH_KILLER_RE.captures(line)?;
+
+    let victim_geid = victim[2].to_string();
+    let killer_known = &killer[1] != "unknown" && &killer[2] != "0";
+    let capture = |re: &Regex| re.captures(line).map(|c| c[1].to_string());
+
+    Some(ActorDeath {
+        victim: victim[1].to_string(),
+        suicide: killer_known && killer[2] == victim_geid,
+        victim_geid,
+        killer: killer_known.then(|| killer[1].to_string()),
+        killer_geid: killer_known.then(|| killer[2].to_string()),
+        weapon: capture(&DEATH_WEAPON_RE),
+        damage_type: capture(&DEATH_DAMAGE_TYPE_RE),
+        zone: capture(&DEATH_ZONE_RE),
+    })
+}
+
+/// Whether two player names are the same character. SC doesn't keep the case of a
+/// handle consistent between the login and later lines.
+pub fn is_same_player(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+/// Window in which a repeated death counts once, when no other is given
+pub const DEFAULT_DEATH_DEDUP_WINDOW_MS: i64 = 1_000;
+
+/// Recognizes deaths SC logged twice (a state change and the actor death): the same
+/// victim and killer again within `window_ms`
+pub struct DeathDeduper {
+    window_ms: i64,
+    /// Timestamp, victim and killer of the deaths inside the window
+    recent: VecDeque<(i64, String, Option<String>)>,
+}
+
+impl DeathDeduper {
+    /// A window of 0 or less keeps every death
+    pub fn new(window_ms: i64) -> Self {
+        Self {
+            window_ms,
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Whether `death`, logged at `timestamp_ms`, repeats one within the window.
+    /// Deaths without a timestamp can't be placed in a window and always count.
+    pub fn is_duplicate(&mut self, death: &ActorDeath, timestamp_ms: Option<i64>) -> bool {
+        let Some(ts) = timestamp_ms.filter(|_| self.window_ms > 0) else {
+            return false;
+        };
+        while self.recent.front().is_some_and(|(seen, _, _)| ts - seen > self.window_ms) {
+            self.recent.pop_front();
+        }
+        let repeated = self
+            .recent
+            .iter()
+            .any(|(_, victim, killer)| *victim == death.victim && *killer == death.killer);
+        if !repeated {
+            self.recent.push_back((ts, death.victim.clone(), death.killer.clone()));
+        }
+        repeated
+    }
+}
+
+/// Whether the player took or gave up control of a vehicle
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VehicleAction {
+    Enter,
+    Exit,
+}
+
+/// Vehicle details from a `<Vehicle Control Flow>` line
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct VehicleEvent {
+    pub action: VehicleAction,
+    /// Class with the instance id and engine suffixes stripped (`RSI_Constellation_Andromeda`)
+    pub vehicle_class: String,
+    pub vehicle_geid: String,
+    /// Seat name, on lines that log one
+    pub seat: Option<String>,
+}
+
+/// Strip the numeric instance suffix and engine parts (`_PU_`, `_AI_`, ...) from a
+/// vehicle entity name, e.g. `ANVL_Hornet_F7C_PU_AI_FF_789` -> `ANVL_Hornet_F7C`
+pub fn vehicle_class(raw: &str) -> String {
+    let mut parts: Vec<&str> = raw.split('_').collect();
+    if parts.len() > 1 && parts.last().is_some_and(|p| p.chars().all(|c| c.is_ascii_digit())) {
+        parts.pop();
+    }
+    parts.retain(|part| !VEHICLE_TECHNICAL_PARTS.contains(part));
+    parts.join("_")
+}
+
+/// Parse a `<Vehicle Control Flow>` line into an enter/exit event.
+/// Control requests and placeholder `Default_*` vehicles yield None.
+pub fn parse_vehicle_control(line: &str) -> Option<VehicleEvent> {
+    if !line.contains("<Vehicle Control Flow>") {
+        return None;
+    }
+
+    let caps = VEHICLE_CONTROL_RE.captures(line)?;
+    let action = match &caps[1] {
+        "granted" => VehicleAction::Enter,
+        "releasing" => VehicleAction::Exit,
+        _ => return None,
+    };
+
+    let raw_name = &caps[2];
+    if raw_name == "Default" || raw_name.starts_with("Default_") {
+        return None;
+    }
+
+    Some(VehicleEvent {
+        action,
+        vehicle_class: vehicle_class(raw_name),
+        vehicle_geid: caps[3].to_string(),
+        seat: VEHICLE_SEAT_RE.captures(line).map(|c| c[1].to_string()),
+    })
+}
+
+/// Quantum drive state transition from a `<Jump Drive Requesting State Change>` line
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct JumpDriveState {
+    pub from_state: String,
+    pub to_state: String,
+    /// Readable name of `to_state` (see spool_state_name)
+    pub state: String,
+    pub reason: String,
+    /// Raw vehicle entity name
+    pub vehicle: Option<String>,
+    /// Zone the vehicle was in when the change was requested
+    pub zone: Option<String>,
+    pub timestamp_ms: Option<i64>,
+}
+
+/// Readable name of an internal jump drive state token: Idle, Spooling, Engaged or
+/// Cooldown. Tokens not known yet are passed through unchanged.
+pub fn spool_state_name(token: &str) -> String {
+    match token.to_ascii_lowercase().as_str() {
+        "idle" => "Idle",
+        "prep" | "spooling" | "spoolup" | "charging" => "Spooling",
+        "engaged" | "engage" | "jumping" | "traveling" | "travelling" => "Engaged",
+        "cooldown" | "cooling" | "coolingdown" => "Cooldown",
+        _ => token,
+    }
+    .to_string()
+}
+
+/// Parse a `<Jump Drive Requesting State Change>` line
+pub fn parse_jump_drive_state(line: &str) -> Option<JumpDriveState> {
+    if !line.contains("<Jump Drive Requesting State Change>") {
+        return None;
+    }
+
+    let caps = JUMP_DRIVE_STATE_RE.captures(line)?;
+    let location = JUMP_DRIVE_ZONE_RE.captures(line);
+
+    Some(JumpDriveState {
+        from_state: caps[1].to_string(),
+        to_state: caps[2].to_string(),
+        state: spool_state_name(&caps[2]),
+        reason: caps[3].trim().to_string(),
+        vehicle: location.as_ref().map(|c| c[1].to_string()),
+        zone: location.map(|c| c[2].trim().to_string()),
+        timestamp_ms: crate::events::parse_timestamp(line),
+    })
+}
+
+/// Item class prefixes dropped from display names
+const ITEM_CLASS_PREFIXES: &[&str] = &["Clothing_", "Armor_", "Weapon_"];
+
+/// Inventory line kind
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum InventoryAction {
+    /// `<EquipItem>`: moved from an inventory onto the player
+    Equip,
+    /// `<AttachmentReceived>`: attached to a port (weapon attachments, worn items)
+    Attach,
+}
+
+/// Item details from an `<EquipItem>` or `<AttachmentReceived>` line
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct InventoryEvent {
+    pub action: InventoryAction,
+    pub item_class: String,
+    /// `item_class` without category prefixes and instance ids (`Armor_Heavy_Chest` -> `Heavy Chest`)
+    pub display_name: String,
+    /// Inventory the item came from (`Location`, ...); equips only
+    pub slot: Option<String>,
+    pub port: Option<String>,
+    /// PostAction of an equip (`Wear`, `Carry`) or Status of an attachment
+    pub state: Option<String>,
+}
+
+/// Human-readable name for an item class
+pub fn item_display_name(item_class: &str) -> String {
+    let mut name = item_class;
+    if let Some(rest) = name.strip_prefix("Carryable_") {
+        // Carryable_<Category>_<Item>
+        name = rest.split_once('_').map_or(rest, |(_, item)| item);
+    }
+    if let Some(prefix) = ITEM_CLASS_PREFIXES.iter().find(|prefix| name.starts_with(*prefix)) {
+        name = &name[prefix.len()..];
+    }
+
+    // Instance ids are long; short numbers are variants (`_01`) and stay
+    let mut parts: Vec<&str> = name.split('_').filter(|part| !part.is_empty()).collect();
+    let is_instance_id = |part: &&str| part.len() >= 4 && part.chars().all(|c| c.is_ascii_digit());
+    if parts.len() > 1 && parts.last().is_some_and(is_instance_id) {
+        parts.pop();
+    }
+
+    let joined = parts.join(" ");
+    let mut chars = joined.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => item_class.to_string(),
+    }
+}
+
+/// Parse an `<EquipItem>` or `<AttachmentReceived>` line
+pub fn parse_inventory_event(line: &str) -> Option<InventoryEvent> {
+    let capture = |re: &Regex| re.captures(line).map(|c| c[1].to_string());
+
+    let (action, item_class, slot, state) = if line.contains("<EquipItem>") {
+        (
+            InventoryAction::Equip,
+            capture(&ITEM_CLASS_RE)?,
+            capture(&ITEM_INVENTORY_RE),
+            capture(&ITEM_POST_ACTION_RE),
+        )
+    } else if line.contains("<AttachmentReceived>") {
+        // Attachment[name_id, class, id] on live builds, Attachment[class] on older ones
+        let attachment = capture(&ATTACHMENT_RE)?;
+        let mut fields = attachment.split(',').map(str::trim);
+        let first = fields.next().unwrap_or_default().to_string();
+        let item_class = fields.next().map_or(first, str::to_string);
+        (InventoryAction::Attach, item_class, None, capture(&ATTACHMENT_STATUS_RE))
+    } else {
+        return None;
+    };
+
+    Some(InventoryEvent {
+        action,
+        display_name: item_display_name(&item_class),
+        item_class,
+        slot,
+        port: capture(&ITEM_PORT_RE),
+        state,
+    })
+}
+
+/// Vehicle that finished a quantum jump, from a `<Quantum Drive Arrived` line
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct QuantumArrival {
+    pub vehicle_class: String,
+    pub vehicle_geid: String,
+}
+
+/// Parse a `<Quantum Drive Arrived` line. The line names the vehicle but not the zone.
+pub fn parse_quantum_arrival(line: &str) -> Option<QuantumArrival> {
+    if !line.contains("<Quantum Drive Arrived") {
+        return None;
+    }
+
+    let caps = QUANTUM_ARRIVAL_VEHICLE_RE.captures(line)?;
+    Some(QuantumArrival {
+        vehicle_class: vehicle_class(&caps[1]),
+        vehicle_geid: caps[2].to_string(),
+    })
+}
+
+/// Mission progress from the mission event lines
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct MissionEvent {
+    pub mission_id: String,
+    /// Contract name, only logged when an objective marker is created
+    pub mission_name: Option<String>,
+    /// Objective id as logged; the log references objectives by GUID, not by text
+    pub objective: Option<String>,
+    /// Objective or mission state (`MISSION_OBJECTIVE_STATE_COMPLETED`, `Complete`, ...)
+    pub state: Option<String>,
+}
+
+/// Parse a `<MissionShared>`, `<ObjectiveUpserted>`, `<MissionEnded>`, `<EndMission>`
+/// or objective marker line
+pub fn parse_mission_event(line: &str) -> Option<MissionEvent> {
+    let capture = |re: &Regex| re.captures(line).map(|c| c[1].to_string());
+    let event = |mission_id: String| MissionEvent {
+        mission_id,
+        mission_name: None,
+        objective: None,
+        state: None,
+    };
+
+    if line.contains("<MissionShared>") {
+        return capture(&MISSION_SHARED_RE).map(event);
+    }
+    if line.contains("<ObjectiveUpserted>") {
+        let caps = OBJECTIVE_UPSERTED_RE.captures(line)?;
+        return Some(MissionEvent {
+            objective: Some(caps[2].to_string()),
+            state: Some(caps[3].to_string()),
+            ..event(caps[1].to_string())
+        });
+    }
+    if line.contains("<MissionEnded>") {
+        let caps = MISSION_ENDED_RE.captures(line)?;
+        return Some(MissionEvent {
+            state: Some(caps[2].to_string()),
+            ..event(caps[1].to_string())
+        });
+    }
+    if line.contains("<EndMission>") {
+        return Some(MissionEvent {
+            state: capture(&END_MISSION_TYPE_RE),
+            ..event(capture(&END_MISSION_ID_RE)?)
+        });
+    }
+    if line.contains("<CLocalMissionPhaseMarker::CreateMarker>") {
+        return Some(MissionEvent {
+            mission_name: capture(&MARKER_CONTRACT_RE),
+            objective: capture(&MARKER_OBJECTIVE_RE),
+            ..event(capture(&MARKER_MISSION_ID_RE)?)
+        });
+    }
+    None
+}
+
+/// World position in metres
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Parse one coordinate, tolerating a leading `+` and uppercase exponents
+fn parse_coordinate(value: &str) -> Option<f64> {
+    let normalized = value.trim().trim_start_matches('+').to_ascii_lowercase();
+    normalized.parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// First `x: .. y: .. z: ..` triple in a line that is a position. Direction vectors
+/// (`from direction x: 0, y: 0, z: 0` on death lines) are skipped.
+pub fn parse_coordinates(line: &str) -> Option<Vec3> {
+    COORDINATES_RE
+        .captures_iter(line)
+        .filter(|caps| !line[..caps.get(0).unwrap().start()].trim_end().ends_with("direction"))
+        .find_map(|caps| {
+            Some(Vec3 {
+                x: parse_coordinate(&caps[1])?,
+                y: parse_coordinate(&caps[2])?,
+                z: parse_coordinate(&caps[3])?,
+            })
+        })
+}
+
+/// Landing/docking line kind
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocationKind {
+    /// `<CLandingArea::OnDoorOpenStateChanged>`: hangar door of a landing area
+    Landing,
+    /// `<CSCItemDockingTube::OnSetTubeState>`: docking tube extended/retracted
+    Docking,
+}
+
+/// Where the player landed or docked, for a places-visited timeline
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct LocationEvent {
+    pub kind: LocationKind,
+    /// Readable name from KNOWN_LOCATIONS, else the raw id as logged
+    pub location_name: Option<String>,
+    /// Door state (`Open`, `Closed`) or new tube state (`Extended`, `Retracted`)
+    pub state: Option<String>,
+}
+
+/// Readable name for an internal location id, or the id itself if it isn't known
+pub fn location_display_name(id: &str) -> String {
+    let lower = id.to_ascii_lowercase();
+    KNOWN_LOCATIONS
+        .iter()
+        .find(|(key, _)| lower.contains(key))
+        .map_or_else(|| id.to_string(), |(_, name)| name.to_string())
+}
+
+/// Parse a landing area door or docking tube line
+pub fn parse_location_event(line: &str) -> Option<LocationEvent> {
+    let capture = |re: &Regex| re.captures(line).map(|c| c[1].to_string());
+
+    let (kind, state) = if line.contains("<CLandingArea::OnDoorOpenStateChanged>") {
+        (LocationKind::Landing, capture(&LANDING_STATE_RE))
+    } else if line.contains("<CSCItemDockingTube::OnSetTubeState>") {
+        (LocationKind::Docking, capture(&DOCKING_TUBE_STATE_RE))
+    } else {
+        return None;
+    };
+
+    // A station/planet id names the place better than the landing area id
+    let location_id = capture(&LOCATION_ID_RE).or_else(|| capture(&LANDING_AREA_RE));
+    if location_id.is_none() && state.is_none() {
+        return None;
+    }
+
+    Some(LocationEvent {
+        kind,
+        location_name: location_id.as_deref().map(location_display_name),
+        state,
+    })
+}
+
+/// Spending line kind
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EconomyKind {
+    /// `<CEntityComponentShoppingProvider::SendStandardItemBuyRequest>`
+    Purchase,
+    /// `<CWallet::ProcessClaimToNextStep>`: insurance claim
+    Claim,
+}
+
+/// Money spent (or a claim filed), for a session spend tracker
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct EconomyEvent {
+    pub kind: EconomyKind,
+    /// Item bought, or the claimed entitlement URN
+    pub item_or_reason: String,
+    /// aUEC, when the line states one
+    pub amount: Option<f64>,
+    pub shop: Option<String>,
+}
+
+/// Parse a purchase or insurance claim line
+pub fn parse_economy_event(line: &str) -> Option<EconomyEvent> {
+    let capture = |re: &Regex| re.captures(line).map(|c| c[1].to_string());
+    let amount = |re: &Regex| capture(re).and_then(|value| value.trim().parse::<f64>().ok());
+
+    if line.contains("<CEntityComponentShoppingProvider::SendStandardItemBuyRequest>") {
+        return Some(EconomyEvent {
+            kind: EconomyKind::Purchase,
+            item_or_reason: capture(&PURCHASE_ITEM_RE)?,
+            amount: amount(&PURCHASE_PRICE_RE),
+            shop: capture(&PURCHASE_SHOP_RE),
+        });
+    }
+    if line.contains("<CWallet::ProcessClaimToNextStep>") {
+        return Some(EconomyEvent {
+            kind: EconomyKind::Claim,
+            item_or_reason: capture(&CLAIM_URN_RE)?,
+            amount: amount(&CLAIM_AMOUNT_RE),
+            shop: None,
+        });
+    }
+    None
+}
+
+/// One `<[STAMINA]` line, for an exertion timeline
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct StaminaSample {
+    pub timestamp_ms: i64,
+    /// Stamina level when the line states one; state lines (`Player started
+    /// suffocating`) only mark the moment
+    pub value: Option<f64>,
+}
+
+/// Parse a timestamped `<[STAMINA]` line
+pub fn parse_stamina(line: &str) -> Option<StaminaSample> {
+    if !line.contains("<[STAMINA]") {
+        return None;
+    }
+    Some(StaminaSample {
+        timestamp_ms: crate::events::parse_timestamp(line)?,
+        value: STAMINA_VALUE_RE
+            .captures(line)
+            .and_then(|caps| parse_coordinate(&caps[1])),
+    })
+}
+
+/// Vehicle crash, for a "hall of shame" of the worst impacts
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct Collision {
+    /// Vehicle that crashed, as logged (e.g. `AEGS_Gladius_123`)
+    pub entity_a: String,
+    /// What it hit: another vehicle/actor, or a zone when `hit_environment` is set
+    pub entity_b: String,
+    /// Impact speed in m/s, when the line states one
+    pub speed: Option<f64>,
+    pub zone: Option<String>,
+    /// Whether `entity_b` is terrain or another environment zone rather than an actor
+    pub hit_environment: bool,
+}
+
+/// Whether a collision target is the environment. Terrain hits name a zone
+/// (`OOC_Stanton_2b_Daymar`), often the one the vehicle is in.
+fn is_environment_entity(entity: &str, zone: Option<&str>) -> bool {
+    zone == Some(entity) || entity.starts_with("OOC_") || entity.contains("SolarSystem")
+}
+
+/// Parse a `<FatalCollision>` line
+pub fn parse_fatal_collision(line: &str) -> Option<Collision> {
+    if !line.contains("<FatalCollision>") {
+        return None;
+    }
+    let capture = |re: &Regex| re.captures(line).map(|c| c[1].trim().to_string());
+
+    let entity_a = capture(&COLLISION_VEHICLE_RE)?;
+    let entity_b = capture(&COLLISION_HIT_RE).filter(|entity| !entity.is_empty())?;
+    let zone = capture(&COLLISION_ZONE_RE);
+    let speed = capture(&COLLISION_SPEED_RE)
+        .and_then(|speed| speed.parse::<f64>().ok())
+        .filter(|speed| speed.is_finite());
+
+    Some(Collision {
+        hit_environment: is_environment_entity(&entity_b, zone.as_deref()),
+        entity_a,
+        entity_b,
+        speed,
+        zone,
+    })
+}
+
+/// Presence line kind
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceKind {
+    /// `<Spawn Flow>`: (re)spawned at a spawn point
+    Spawn,
+    /// `<CEntity::OnOwnerRemoved>`: detached from its owner (e.g. left a medical bed)
+    Despawn,
+}
+
+/// Player spawning in or leaving, for a presence timeline
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct PresenceEvent {
+    pub kind: PresenceKind,
+    /// Player named on the line, else the entity whose owner was removed
+    pub entity: String,
+    /// Spawn point (or location id) for spawns, zone for despawns
+    pub zone: Option<String>,
+}
+
+impl PresenceEvent {
+    /// Whether the event concerns the logged-in player. OnOwnerRemoved also fires for
+    /// items and vehicles changing hands; with no known player everything is kept.
+    pub fn is_player(&self, player_name: Option<&str>) -> bool {
+        player_name.is_none_or(|name| is_same_player(&self.entity, name))
+    }
+}
+
+/// State change of an elevator or loading platform, from a `<CSCLoadingPlatformManager>` line
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct PlatformEvent {
+    pub platform_id: String,
+    pub state: String,
+    pub timestamp_ms: Option<i64>,
+}
+
+/// Parse a `<CSCLoadingPlatformManager>` line. None unless both the platform and its
+/// new state are logged.
+pub fn parse_platform_event(line: &str) -> Option<PlatformEvent> {
+    if !line.contains("<CSCLoadingPlatformManager>") {
+        return None;
+    }
+
+    let id = PLATFORM_ID_RE.captures(line)?;
+    let platform_id = id.get(1).or_else(|| id.get(2))?.as_str().to_string();
+    let state = PLATFORM_STATE_RE.captures(line)?[1].to_string();
+
+    Some(PlatformEvent {
+        platform_id,
+        state,
+        timestamp_ms: crate::events::parse_timestamp(line),
+    })
+}
+
+/// Parse a spawn or owner-removed line
+pub fn parse_presence_event(line: &str) -> Option<PresenceEvent> {
+    let capture = |re: &Regex| re.captures(line).map(|c| c[1].trim().to_string());
+
+    if line.contains("<Spawn Flow>") {
+        return Some(PresenceEvent {
+            kind: PresenceKind::Spawn,
+            entity: capture(&SPAWN_PLAYER_RE)?,
+            zone: capture(&SPAWN_POINT_RE).or_else(|| capture(&SPAWN_LOCATION_RE)),
+        });
+    }
+    if line.contains("<CEntity::OnOwnerRemoved>") {
+        return Some(PresenceEvent {
+            kind: PresenceKind::Despawn,
+            entity: capture(&OWNER_REMOVED_PLAYER_RE).or_else(|| capture(&OWNER_REMOVED_ENTITY_RE))?,
+            zone: capture(&OWNER_REMOVED_ZONE_RE),
+        });
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_actor_death_player_kill() {
+        let line = "<2025-11-02T07:47:10.855Z> [Notice] <Actor Death> CActor::Kill: 'Victim' [123] in zone 'AEGS_Gladius_456' killed by 'Killer' [789] using 'KLWE_LaserRepeater_S2_123456' [Class KLWE_LaserRepeater_S2] with damage type 'VehicleDestruction' from direction x: 0, y: 0, z: 0 [Team_ActorTech][Actor]";
+        let death = parse_actor_death(line).unwrap();
+
+        assert_eq!(death.victim, "Victim");
+        assert_eq!(death.victim_geid, "123");
+        assert_eq!(death.killer.as_deref(), Some("Killer"));
+        assert_eq!(death.killer_geid.as_deref(), Some("789"));
+        assert_eq!(death.weapon.as_deref(), Some("KLWE_LaserRepeater_S2_123456"));
+        assert_eq!(death.damage_type.as_deref(), Some("VehicleDestruction"));
+        assert_eq!(death.zone.as_deref(), Some("AEGS_Gladius_456"));
+        assert!(!death.suicide);
+    }
+
+    #[test]
+    fn test_parse_actor_death_npc_names_with_underscores() {
+        let line = "<2025-11-02T07:47:10.855Z> <Actor Death> CActor::Kill: 'NPC_Archetypes-Male-Human-FrontierFighters_Pilot_123' [200146296879] in zone 'zone' killed by 'PU_SecurityGuard_NPC' [201990709919] using 'weapon' with damage type 'Combat'";
+        let death = parse_actor_death(line).unwrap();
+
+        assert_eq!(death.victim, "NPC_Archetypes-Male-Human-FrontierFighters_Pilot_123");
+        assert_eq!(death.victim_geid, "200146296879");
+        assert_eq!(death.killer.as_deref(), Some("PU_SecurityGuard_NPC"));
+    }
+
+    #[test]
+    fn test_parse_actor_death_unknown_killer() {
+        let line = "<2025-11-02T07:47:10.855Z> <Actor Death> CActor::Kill: 'Player' [123] in zone 'Stanton' killed by 'unknown' [0] using 'unknown' with damage type 'Crash'";
+        let death = parse_actor_death(line).unwrap();
+
+        assert_eq!(death.killer, None);
+        assert_eq!(death.killer_geid, None);
+        assert_eq!(death.damage_type.as_deref(), Some("Crash"));
+        assert!(!death.suicide);
+    }
+
+    #[test]
+    fn test_parse_actor_death_suicide() {
+        let line = "<2025-11-02T07:47:10.855Z> <Actor Death> CActor::Kill: 'Player' [123] in zone 'zone' killed by 'Player' [123] using 'unknown' with damage type 'Suicide'";
+        assert!(parse_actor_death(line).unwrap().suicide);
+    }
+
+    #[test]
+    fn test_parse_actor_death_rejects_other_lines() {
+        assert!(parse_actor_death("<2025-11-02T07:47:10.855Z> <SystemQuit> CSystem::Quit invoked...").is_none());
+        // Marker present but fields missing
+        assert!(parse_actor_death("<2025-11-02T07:47:10.855Z> <Actor Death> malformed").is_none());
+    }
+
+    #[test]
+    fn test_parse_vehicle_control_enter_and_exit() {
+        let enter = "<2025-11-02T07:47:10.855Z> [Notice] <Vehicle Control Flow> CVehicleMovementBase::SetDriver: Local client node [123] granted control token for 'AEGS_Gladius_456' [456] [Team_VehicleFeatures][Vehicle]";
+        let event = parse_vehicle_control(enter).unwrap();
+
+        assert_eq!(event.action, VehicleAction::Enter);
+        assert_eq!(event.vehicle_class, "AEGS_Gladius");
+        assert_eq!(event.vehicle_geid, "456");
+        assert_eq!(event.seat, None);
+
+        let exit = "<2025-11-02T07:50:00.000Z> <Vehicle Control Flow> Local client node [123] releasing control token for 'AEGS_Gladius_456' [456]";
+        assert_eq!(parse_vehicle_control(exit).unwrap().action, VehicleAction::Exit);
+    }
+
+    #[test]
+    fn test_parse_vehicle_control_ignores_requests_and_defaults() {
+        let requesting = "<2025-11-02T07:47:10.855Z> <Vehicle Control Flow> Local client node [123] requesting control token for 'AEGS_Gladius_456' [456]";
+        let default = "<2025-11-02T07:47:10.855Z> <Vehicle Control Flow> Local client node [123] granted control token for 'Default_17518' [456]";
+
+        assert!(parse_vehicle_control(requesting).is_none());
+        assert!(parse_vehicle_control(default).is_none());
+    }
+
+    #[test]
+    fn test_vehicle_class_strips_suffixes() {
+        assert_eq!(vehicle_class("RSI_Constellation_Andromeda_7174409165064"), "RSI_Constellation_Andromeda");
+        assert_eq!(vehicle_class("ANVL_Hornet_F7C_PU_AI_FF_789"), "ANVL_Hornet_F7C");
+        assert_eq!(vehicle_class("ANVL_Valkyrie_PU_Al_FF_7347713114638"), "ANVL_Valkyrie");
+    }
+
+    #[test]
+    fn test_parse_jump_drive_state() {
+        let line = "<2025-11-02T07:47:10.855Z> [Notice] <Jump Drive Requesting State Change> Requested change from Idle to Spooling, reason: QDRV spooling | Stanton | (player: AEGS_GLADIUS_7123456789 in zone Crusader) [Team_CGP4][QuantumTravel]";
+        let state = parse_jump_drive_state(line).unwrap();
+
+        assert_eq!(state.from_state, "Idle");
+        assert_eq!(state.to_state, "Spooling");
+        assert_eq!(state.state, "Spooling");
+        assert_eq!(state.timestamp_ms, Some(1_762_069_630_855));
+        assert_eq!(state.reason, "QDRV spooling");
+        assert_eq!(state.vehicle.as_deref(), Some("AEGS_GLADIUS_7123456789"));
+        assert_eq!(state.zone.as_deref(), Some("Crusader"));
+
+        assert!(parse_jump_drive_state("<2025-11-02T07:47:10.855Z> <Jump Drive Requesting State Change> malformed").is_none());
+    }
+
+    #[test]
+    fn test_spool_state_name() {
+        assert_eq!(spool_state_name("Idle"), "Idle");
+        assert_eq!(spool_state_name("Prep"), "Spooling");
+        assert_eq!(spool_state_name("Travelling"), "Engaged");
+        assert_eq!(spool_state_name("CoolingDown"), "Cooldown");
+        assert_eq!(spool_state_name("Calibrating"), "Calibrating");
+    }
+
+    #[test]
+    fn test_parse_quantum_arrival() {
+        let line = "<2025-11-02T07:47:10.855Z> [Notice] <Quantum Drive Arrived - Arrived at Final Destination> [ItemNavigation][CL][14316] | NOT AUTH | RSI_Perseus_7724689695195[7724689695195]|CSCItemNavigation::OnQuantumDriveArrived|Quantum Drive has arrived at final destination [Team_CGP4][QuantumTravel]";
+        let arrival = parse_quantum_arrival(line).unwrap();
+
+        assert_eq!(arrival.vehicle_class, "RSI_Perseus");
+        assert_eq!(arrival.vehicle_geid, "7724689695195");
+    }
+
+    #[test]
+    fn test_parse_mission_event_objective_and_end() {
+        let upsert = "<2025-11-02T07:47:10.855Z> [Notice] <ObjectiveUpserted> Received ObjectiveUpserted push message for: mission_id abc-123 - objective_id 9f1e-77aa - state MISSION_OBJECTIVE_STATE_COMPLETED [Team_Missions]";
+        let event = parse_mission_event(upsert).unwrap();
+        assert_eq!(event.mission_id, "abc-123");
+        assert_eq!(event.objective.as_deref(), Some("9f1e-77aa"));
+        assert_eq!(event.state.as_deref(), Some("MISSION_OBJECTIVE_STATE_COMPLETED"));
+
+        let ended = "<2025-11-02T07:50:00.000Z> <MissionEnded> Received MissionEnded push message for: mission_id abc-123 - mission_state MISSION_STATE_COMPLETED";
+        assert_eq!(parse_mission_event(ended).unwrap().state.as_deref(), Some("MISSION_STATE_COMPLETED"));
+
+        let end = "<2025-11-02T07:50:00.000Z> <EndMission> Ending mission for player. MissionId[abc-123] Player[TestPlayer] PlayerId[123] CompletionType[Abort] Reason[PlayerAborted]";
+        let event = parse_mission_event(end).unwrap();
+        assert_eq!(event.mission_id, "abc-123");
+        assert_eq!(event.state.as_deref(), Some("Abort"));
+    }
+
+    #[test]
+    fn test_parse_mission_event_marker_name_and_shared() {
+        let marker = "<2025-11-02T07:47:10.855Z> <CLocalMissionPhaseMarker::CreateMarker> Creating objective marker: missionId [abc-123], generator name [InterSec_KillShip], contract [InterSec_Bounty_Nyx_Easy], objectiveId [obj-1]";
+        let event = parse_mission_event(marker).unwrap();
+        assert_eq!(event.mission_name.as_deref(), Some("InterSec_Bounty_Nyx_Easy"));
+        assert_eq!(event.objective.as_deref(), Some("obj-1"));
+
+        let shared = "<2025-11-02T07:47:10.855Z> <MissionShared> Received share push message: ownerId[123] - missionId[abc-123-def-456]";
+        assert_eq!(parse_mission_event(shared).unwrap().mission_id, "abc-123-def-456");
+        assert!(parse_mission_event("<2025-11-02T07:47:10.855Z> <MissionEnded> malformed").is_none());
+    }
+
+    #[test]
+    fn test_parse_coordinates_scientific_notation() {
+        let line = "<2025-11-02T07:47:10.855Z> <[ActorState] Dead> Player 'Pilot' died at position x: 1.2345e+07, y: -2.5E-3, z: +42 [Team_ActorTech]";
+        assert_eq!(parse_coordinates(line), Some(Vec3 { x: 1.2345e7, y: -0.0025, z: 42.0 }));
+    }
+
+    #[test]
+    fn test_parse_coordinates_skips_direction_vectors() {
+        let death = "<2025-11-02T07:47:10.855Z> <Actor Death> CActor::Kill: 'A' [1] killed by 'B' [2] with damage type 'Crash' from direction x: 0, y: 0, z: 1 [Team_ActorTech]";
+        assert_eq!(parse_coordinates(death), None);
+        assert_eq!(parse_coordinates("<2025-11-02T07:47:10.855Z> no coordinates"), None);
+    }
+
+    #[test]
+    fn test_parse_inventory_event_equip() {
+        let line = "<2025-11-02T07:47:10.855Z> [Notice] <EquipItem> Request[123] equip from Inventory[456:Location:789] Class[Armor_Heavy_Chest] Rank[1] Port[Torso] DependentRequest[] PostAction[Wear]";
+        let event = parse_inventory_event(line).unwrap();
+
+        assert_eq!(event.action, InventoryAction::Equip);
+        assert_eq!(event.item_class, "Armor_Heavy_Chest");
+        assert_eq!(event.display_name, "Heavy Chest");
+        assert_eq!(event.slot.as_deref(), Some("Location"));
+        assert_eq!(event.port.as_deref(), Some("Torso"));
+        assert_eq!(event.state.as_deref(), Some("Wear"));
+    }
+
+    #[test]
+    fn test_parse_inventory_event_attachment_formats() {
+        let live = "<2025-11-02T07:47:10.855Z> <AttachmentReceived> Player[Pilot] Attachment[behr_rifle_ballistic_01_2001, behr_rifle_ballistic_01, 2001] Status[persistent] Port[wep_stocked_2]";
+        let event = parse_inventory_event(live).unwrap();
+        assert_eq!(event.action, InventoryAction::Attach);
+        assert_eq!(event.item_class, "behr_rifle_ballistic_01");
+        assert_eq!(event.port.as_deref(), Some("wep_stocked_2"));
+        assert_eq!(event.state.as_deref(), Some("persistent"));
+
+        let short = "<2025-11-02T07:47:10.855Z> <AttachmentReceived> Player[Pilot] Attachment[LaserRifle] Status[Equipped] Port[RightHand]";
+        assert_eq!(parse_inventory_event(short).unwrap().item_class, "LaserRifle");
+    }
+
+    #[test]
+    fn test_item_display_name() {
+        assert_eq!(item_display_name("Carryable_Food_Apple"), "Apple");
+        assert_eq!(item_display_name("Clothing_Jacket_01_1234"), "Jacket 01");
+        assert_eq!(item_display_name("behr_rifle_ballistic_01"), "Behr rifle ballistic 01");
+    }
+
+    #[test]
+    fn test_parse_landing_event() {
+        let line = "<2025-11-02T07:47:10.855Z> [Notice] <CLandingArea::OnDoorOpenStateChanged> LandingArea_Hangar_MedFront [Stanton1_Lorville] Door: HangarDoor_01 State: Open [Team_Locations]";
+        assert_eq!(
+            parse_location_event(line),
+            Some(LocationEvent {
+                kind: LocationKind::Landing,
+                location_name: Some("Lorville".to_string()),
+                state: Some("Open".to_string()),
+            })
+        );
+
+        // Unknown places keep their raw id
+        let line = "<2025-11-02T07:47:10.855Z> [Notice] <CLandingArea::OnDoorOpenStateChanged> LandingArea_Outpost_7 Door: HangarDoor_01 State: Closed";
+        let event = parse_location_event(line).unwrap();
+        assert_eq!(event.location_name.as_deref(), Some("LandingArea_Outpost_7"));
+    }
+
+    #[test]
+    fn test_parse_docking_event() {
+        let line = "<2025-11-02T07:47:10.855Z> [Notice] <CSCItemDockingTube::OnSetTubeState> DockingTube at RR_MIC_LEO OnSetTubeState to Extended, last tube state is Retracted";
+        assert_eq!(
+            parse_location_event(line),
+            Some(LocationEvent {
+                kind: LocationKind::Docking,
+                location_name: Some("Port Tressler".to_string()),
+                state: Some("Extended".to_string()),
+            })
+        );
+        assert_eq!(location_display_name("stanton2b"), "Daymar");
+        assert!(parse_location_event("<CSCItemDockingTube::OnSetTubeState> nothing useful").is_none());
+    }
+
+    #[test]
+    fn test_parse_economy_event() {
+        let line = "<2025-11-02T07:47:10.855Z> <CEntityComponentShoppingProvider::SendStandardItemBuyRequest> itemName[LaserRifle] client_price[5000] shopName[Weapon Shop]";
+        assert_eq!(
+            parse_economy_event(line),
+            Some(EconomyEvent {
+                kind: EconomyKind::Purchase,
+                item_or_reason: "LaserRifle".to_string(),
+                amount: Some(5000.0),
+                shop: Some("Weapon Shop".to_string()),
+            })
+        );
+
+        let line = "<2025-11-02T07:47:10.855Z> <CWallet::ProcessClaimToNextStep> entitlementURN: urn:insurance:claim:123456";
+        assert_eq!(
+            parse_economy_event(line),
+            Some(EconomyEvent {
+                kind: EconomyKind::Claim,
+                item_or_reason: "urn:insurance:claim:123456".to_string(),
+                amount: None,
+                shop: None,
+            })
+        );
+        assert!(parse_economy_event("<CWallet::ProcessClaimToNextStep> no urn").is_none());
+    }
+
+    #[test]
+    fn test_parse_stamina() {
+        let line = "<2025-11-02T07:47:10.855Z> <[STAMINA] Player stamina update> Player[TestPlayer] Stamina: 0.42";
+        assert_eq!(
+            parse_stamina(line),
+            Some(StaminaSample {
+                timestamp_ms: 1_762_069_630_855,
+                value: Some(0.42),
+            })
+        );
+
+        let line = "<2025-11-02T07:47:10.855Z> <[STAMINA] Player started suffocating> Player[TestPlayer] Details: ...";
+        assert_eq!(parse_stamina(line).unwrap().value, None);
+        assert!(parse_stamina("<[STAMINA] Player started suffocating> no timestamp").is_none());
+    }
+
+    #[test]
+    fn test_parse_fatal_collision() {
+        let line = "<2025-11-02T07:47:10.855Z> [Notice] <FatalCollision> Fatal Collision occured for vehicle ANVL_Paladin_123 [Part: ANVL_Paladin_123, Pos: x: 1, y: 2, z: 3, Zone: OOC_Stanton_2b_Daymar, PlayerPilot: 1] after hitting entity: Other-Pilot [Zone: AEGS_Gladius_9 - Class(AEGS_Gladius)] speed: 143.25 [Team_VehicleFeatures]";
+        assert_eq!(
+            parse_fatal_collision(line),
+            Some(Collision {
+                entity_a: "ANVL_Paladin_123".to_string(),
+                entity_b: "Other-Pilot".to_string(),
+                speed: Some(143.25),
+                zone: Some("OOC_Stanton_2b_Daymar".to_string()),
+                hit_environment: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_fatal_collision_terrain() {
+        let line = "<2025-11-02T07:47:10.855Z> [Notice] <FatalCollision> Fatal Collision occured for vehicle AEGS_Gladius_9 [Part: wing_left, Pos: x: 1, y: 2, z: 3, Zone: OOC_Stanton_2b_Daymar, PlayerPilot: 1] after hitting entity: OOC_Stanton_2b_Daymar [Zone: OOC_Stanton_2b_Daymar]. [Team_VehicleFeatures]";
+        let collision = parse_fatal_collision(line).unwrap();
+        assert_eq!(collision.entity_b, "OOC_Stanton_2b_Daymar");
+        assert!(collision.hit_environment);
+        assert_eq!(collision.speed, None);
+
+        assert!(parse_fatal_collision("<FatalCollision> Fatal Collision occured for vehicle AEGS_Gladius_1 at pos x: 1, y: 2, z: 3").is_none());
+    }
+
+    #[test]
+    fn test_parse_presence_event() {
+        let line = "<2025-11-02T07:47:10.855Z> [Notice] <Spawn Flow> CSCPlayerPUSpawningComponent::UnregisterFromExternalSystems: Player 'Pilot' [201990709704] lost reservation for spawnpoint Bed_Single_Front_Sleep-001 [4631877687] at location 5119 [Team_ActorFeatures][Respawn]";
+        assert_eq!(
+            parse_presence_event(line),
+            Some(PresenceEvent {
+                kind: PresenceKind::Spawn,
+                entity: "Pilot".to_string(),
+                zone: Some("Bed_Single_Front_Sleep-001".to_string()),
+            })
+        );
+
+        let own = "<2025-11-02T07:48:00.000Z> [Notice] <CEntity::OnOwnerRemoved> medical bed detached Player[Pilot] Zone[Hospital_Lorville]";
+        let other = "<2025-11-02T07:48:00.000Z> [Notice] <CEntity::OnOwnerRemoved> Entity [crate_box_01] owner removed";
+        let own = parse_presence_event(own).unwrap();
+        let other = parse_presence_event(other).unwrap();
+        assert_eq!(own.kind, PresenceKind::Despawn);
+        assert_eq!(own.zone.as_deref(), Some("Hospital_Lorville"));
+        assert!(own.is_player(Some("Pilot")));
+        assert!(own.is_player(Some("pilot")));
+        assert!(!other.is_player(Some("Pilot")));
+        assert!(other.is_player(None));
+    }
+
+    #[test]
+    fn test_parse_platform_event() {
+        let line = "<2025-11-02T07:47:10.855Z> [Notice] <CSCLoadingPlatformManager> [LoadingPlatformManager_ShipElevator_HangarMediumTop] Platform state changed from Closed to OpeningLoadingGate [Team_CGP1][LoadingPlatform]";
+        assert_eq!(
+            parse_platform_event(line),
+            Some(PlatformEvent {
+                platform_id: "LoadingPlatformManager_ShipElevator_HangarMediumTop".to_string(),
+                state: "OpeningLoadingGate".to_string(),
+                timestamp_ms: Some(1_762_069_630_855),
+            })
+        );
+
+        let bracketed = "<CSCLoadingPlatformManager> platform[Elevator_12] state[Moving]";
+        let event = parse_platform_event(bracketed).unwrap();
+        assert_eq!((event.platform_id.as_str(), event.state.as_str()), ("Elevator_12", "Moving"));
+
+        assert!(parse_platform_event("<CSCLoadingPlatformManager> Platform manager initialized").is_none());
+    }
+}
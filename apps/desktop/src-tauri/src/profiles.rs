@@ -0,0 +1,446 @@
+//! Log format profiles: the timestamp and severity regexes, timestamp format and
+//! marker list that drive the scanner, so logs from other games can be read without
+//! code changes. Timestamps are always normalized to UTC epoch milliseconds.
+//!
+//! The Star Citizen profile is built in and used when a command gets no profile id.
+//! Other profiles are registered from the frontend and persisted like marker rules.
+//! export_profile/import_profile move the whole reading setup between installs as
+//! JSON, so a profile that survives a patch can be shared.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::LogError;
+use crate::markers::{self, MarkerRule};
+use crate::{contains_event_marker, PlayerNameConfig, SEVERITY_RE, TIMESTAMP_RE};
+
+/// Id of the built-in Star Citizen profile
+pub const DEFAULT_PROFILE_ID: &str = "star_citizen";
+
+/// Store key registered profiles are persisted under (in crate::SETTINGS_STORE)
+const LOG_PROFILES_KEY: &str = "logProfiles";
+
+/// A profile as configured by the frontend
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct LogProfileConfig {
+    pub id: String,
+    /// Matches the timestamp at the start of a line (anchor it with `^`). The first
+    /// capture group, if any, is the timestamp text; otherwise the whole match is.
+    pub timestamp_regex: String,
+    /// chrono format of the timestamp text (e.g. `%Y-%m-%d %H:%M:%S%.3f %z`).
+    /// None = RFC 3339, as Star Citizen logs it.
+    #[serde(default)]
+    pub timestamp_format: Option<String>,
+    /// Offset from UTC of timestamps whose format carries none (local-time logs).
+    /// None = UTC.
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+    /// First capture group is the severity
+    pub severity_regex: String,
+    /// Literal substrings marking interesting lines
+    pub markers: Vec<String>,
+}
+
+/// A compiled profile
+#[derive(Debug)]
+pub struct LogProfile {
+    pub id: String,
+    pub timestamp_re: Regex,
+    timestamp_format: Option<String>,
+    /// Applied to timestamps without an offset of their own
+    utc_offset: FixedOffset,
+    pub severity_re: Regex,
+    /// None = the active SC markers (EVENT_MARKERS or set_event_markers, plus marker rules)
+    markers: Option<Vec<String>>,
+}
+
+impl LogProfile {
+    fn star_citizen() -> Self {
+        Self {
+            id: DEFAULT_PROFILE_ID.to_string(),
+            timestamp_re: TIMESTAMP_RE.clone(),
+            timestamp_format: None,
+            utc_offset: FixedOffset::east_opt(0).unwrap(),
+            severity_re: SEVERITY_RE.clone(),
+            markers: None,
+        }
+    }
+
+    /// Compile a profile, failing on an invalid regex, a severity regex without a group,
+    /// an invalid timestamp format or an out-of-range UTC offset
+    pub fn compile(config: LogProfileConfig) -> Result<Self, String> {
+        let timestamp_re = Regex::new(&config.timestamp_regex)
+            .map_err(|e| format!("Invalid timestamp regex '{}': {}", config.timestamp_regex, e))?;
+        let severity_re = Regex::new(&config.severity_regex)
+            .map_err(|e| format!("Invalid severity regex '{}': {}", config.severity_regex, e))?;
+        if severity_re.captures_len() < 2 {
+            return Err(format!("Severity regex '{}' needs a capture group", config.severity_regex));
+        }
+        if let Some(format) = &config.timestamp_format {
+            if StrftimeItems::new(format).any(|item| item == Item::Error) {
+                return Err(format!("Invalid timestamp format '{}'", format));
+            }
+        }
+        let offset_minutes = config.utc_offset_minutes.unwrap_or(0);
+        let utc_offset = FixedOffset::east_opt(offset_minutes * 60)
+            .ok_or_else(|| format!("UTC offset of {} minutes is out of range", offset_minutes))?;
+
+        Ok(Self {
+            id: config.id,
+            timestamp_re,
+            timestamp_format: config.timestamp_format,
+            utc_offset,
+            severity_re,
+            // An empty marker would match every line
+            markers: Some(config.markers.into_iter().filter(|m| !m.is_empty()).collect()),
+        })
+    }
+
+    /// The configuration this profile was compiled from
+    pub fn config(&self) -> LogProfileConfig {
+        LogProfileConfig {
+            id: self.id.clone(),
+            timestamp_regex: self.timestamp_re.as_str().to_string(),
+            timestamp_format: self.timestamp_format.clone(),
+            utc_offset_minutes: Some(self.utc_offset.local_minus_utc() / 60).filter(|&minutes| minutes != 0),
+            severity_regex: self.severity_re.as_str().to_string(),
+            markers: self.markers.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Leading timestamp of a line as UTC epoch milliseconds, or None if the line has
+    /// none or it doesn't parse in this profile's format
+    pub fn parse_timestamp(&self, line: &str) -> Option<i64> {
+        let caps = self.timestamp_re.captures(line)?;
+        let text = caps.get(1).or_else(|| caps.get(0))?.as_str();
+        let text = text.trim().trim_start_matches('<').trim_end_matches('>');
+
+        let Some(format) = &self.timestamp_format else {
+            return DateTime::parse_from_rfc3339(text).ok().map(|dt| dt.timestamp_millis());
+        };
+        // A format with an offset (%z) says where it is; otherwise apply the profile's
+        if let Ok(dt) = DateTime::parse_from_str(text, format) {
+            return Some(dt.timestamp_millis());
+        }
+        NaiveDateTime::parse_from_str(text, format)
+            .ok()?
+            .and_local_timezone(self.utc_offset)
+            .single()
+            .map(|dt| dt.timestamp_millis())
+    }
+
+    /// Whether a line contains one of this profile's markers
+    pub fn contains_marker(&self, line: &str) -> bool {
+        match &self.markers {
+            Some(markers) => markers.iter().any(|marker| line.contains(marker.as_str())),
+            None => contains_event_marker(line),
+        }
+    }
+}
+
+lazy_static! {
+    static ref LOG_PROFILES: RwLock<HashMap<String, Arc<LogProfile>>> = RwLock::new(HashMap::from([(
+        DEFAULT_PROFILE_ID.to_string(),
+        Arc::new(LogProfile::star_citizen()),
+    )]));
+}
+
+/// Profile with the given id, or the Star Citizen profile for None
+pub fn get_profile(id: Option<&str>) -> Result<Arc<LogProfile>, LogError> {
+    let id = id.unwrap_or(DEFAULT_PROFILE_ID);
+    LOG_PROFILES
+        .read()
+        .unwrap()
+        .get(id)
+        .cloned()
+        .ok_or_else(|| LogError::UnknownProfile(id.to_string()))
+}
+
+/// The built-in Star Citizen profile
+pub fn default_profile() -> Arc<LogProfile> {
+    get_profile(None).expect("built-in profile is always registered")
+}
+
+/// Add or replace a log profile. The built-in Star Citizen profile can't be replaced
+/// (its markers are configured with set_event_markers). Persisted across restarts.
+#[tauri::command]
+pub fn register_log_profile(profile: LogProfileConfig, app: tauri::AppHandle) -> Result<(), String> {
+    if profile.id == DEFAULT_PROFILE_ID {
+        return Err(format!("Profile '{}' is built in", DEFAULT_PROFILE_ID));
+    }
+    let compiled = LogProfile::compile(profile.clone())?;
+    store_profiles(vec![profile], vec![compiled], &app)
+}
+
+/// Persist `configs` in place of stored profiles with the same ids, then make their
+/// compiled `profiles` available
+fn store_profiles(configs: Vec<LogProfileConfig>, profiles: Vec<LogProfile>, app: &tauri::AppHandle) -> Result<(), String> {
+    use tauri_plugin_store::StoreExt;
+
+    let store = app.store(crate::SETTINGS_STORE).map_err(|e| e.to_string())?;
+    let mut stored: Vec<LogProfileConfig> = store
+        .get(LOG_PROFILES_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default();
+    stored.retain(|config| configs.iter().all(|new| new.id != config.id));
+    stored.extend(configs);
+    store.set(LOG_PROFILES_KEY, serde_json::json!(stored));
+    store.save().map_err(|e| e.to_string())?;
+
+    let mut registered = LOG_PROFILES.write().unwrap();
+    for profile in profiles {
+        registered.insert(profile.id.clone(), Arc::new(profile));
+    }
+    Ok(())
+}
+
+/// Ids of every available profile, built-in first
+#[tauri::command]
+pub fn list_log_profiles() -> Vec<String> {
+    let mut ids: Vec<String> = LOG_PROFILES
+        .read()
+        .unwrap()
+        .keys()
+        .filter(|id| *id != DEFAULT_PROFILE_ID)
+        .cloned()
+        .collect();
+    ids.sort();
+    ids.insert(0, DEFAULT_PROFILE_ID.to_string());
+    ids
+}
+
+/// Version of the export_profile format
+const PROFILE_EXPORT_VERSION: u32 = 1;
+
+/// Everything that decides how logs are read, as exported by export_profile
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct ProfileExport {
+    pub version: u32,
+    /// Markers set with set_event_markers (empty = the compiled EVENT_MARKERS)
+    #[serde(default)]
+    pub event_markers: Vec<String>,
+    #[serde(default)]
+    pub disabled_markers: Vec<String>,
+    #[serde(default)]
+    pub marker_rules: Vec<MarkerRule>,
+    #[serde(default)]
+    pub player_name_config: PlayerNameConfig,
+    /// Registered profiles besides the built-in one
+    #[serde(default)]
+    pub profiles: Vec<LogProfileConfig>,
+}
+
+impl ProfileExport {
+    /// Check every setting, naming the first invalid one, and compile the profiles
+    fn validate(&self) -> Result<Vec<LogProfile>, String> {
+        if self.version > PROFILE_EXPORT_VERSION {
+            return Err(format!("Profile export version {} is newer than this app supports", self.version));
+        }
+        markers::CompiledRules::compile(self.marker_rules.clone())?;
+        self.player_name_config.validate()?;
+        self.profiles
+            .iter()
+            .map(|profile| {
+                if profile.id == DEFAULT_PROFILE_ID {
+                    return Err(format!("Profile '{}' is built in", DEFAULT_PROFILE_ID));
+                }
+                LogProfile::compile(profile.clone()).map_err(|e| format!("Profile '{}': {}", profile.id, e))
+            })
+            .collect()
+    }
+}
+
+/// The active markers, disabled markers, marker rules, login format and registered
+/// profiles as JSON, for sharing a working setup
+#[tauri::command]
+pub fn export_profile() -> Result<String, String> {
+    let mut profiles: Vec<LogProfileConfig> = LOG_PROFILES
+        .read()
+        .unwrap()
+        .values()
+        .filter(|profile| profile.id != DEFAULT_PROFILE_ID)
+        .map(|profile| profile.config())
+        .collect();
+    profiles.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let export = ProfileExport {
+        version: PROFILE_EXPORT_VERSION,
+        event_markers: crate::CUSTOM_EVENT_MARKERS.lock().unwrap().clone(),
+        disabled_markers: crate::DISABLED_MARKERS.lock().unwrap().clone(),
+        marker_rules: markers::active_rules(),
+        player_name_config: crate::player_name_config(),
+        profiles,
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| e.to_string())
+}
+
+/// Load a setup produced by export_profile. Every setting is validated and every
+/// profile compiled first, so an invalid export changes nothing. Profiles are added to (or replace) the registered
+/// ones; the rest replaces the current settings. Persisted across restarts.
+#[tauri::command]
+pub fn import_profile(json: String, app: tauri::AppHandle) -> Result<(), String> {
+    let export: ProfileExport =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid profile export: {}", e))?;
+    let profiles = export.validate()?;
+
+    crate::set_event_markers(export.event_markers, app.clone())?;
+    crate::set_disabled_markers(export.disabled_markers, app.clone())?;
+    markers::set_marker_rules(export.marker_rules, app.clone())?;
+    crate::set_player_name_config(export.player_name_config, app.clone())?;
+    store_profiles(export.profiles, profiles, &app)
+}
+
+/// Compile the profiles persisted by register_log_profile
+pub fn load_log_profiles(app: &tauri::AppHandle) {
+    use tauri_plugin_store::StoreExt;
+
+    let Ok(store) = app.store(crate::SETTINGS_STORE) else {
+        return;
+    };
+    let Some(value) = store.get(LOG_PROFILES_KEY) else {
+        return;
+    };
+
+    let configs = match serde_json::from_value::<Vec<LogProfileConfig>>(value) {
+        Ok(configs) => configs,
+        Err(e) => {
+            eprintln!("[Rust] Ignoring invalid stored log profiles: {}", e);
+            return;
+        }
+    };
+    let mut profiles = LOG_PROFILES.write().unwrap();
+    for config in configs {
+        match LogProfile::compile(config) {
+            Ok(profile) if profile.id != DEFAULT_PROFILE_ID => {
+                profiles.insert(profile.id.clone(), Arc::new(profile));
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("[Rust] Ignoring invalid stored log profile: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(severity_regex: &str) -> LogProfileConfig {
+        LogProfileConfig {
+            id: "other_game".to_string(),
+            timestamp_regex: r"^\d{2}:\d{2}:\d{2} ".to_string(),
+            timestamp_format: None,
+            utc_offset_minutes: None,
+            severity_regex: severity_regex.to_string(),
+            markers: vec!["PlayerKilled".to_string(), String::new()],
+        }
+    }
+
+    #[test]
+    fn test_custom_profile_markers() {
+        let profile = LogProfile::compile(config(r"\b(INFO|WARN|ERROR)\b")).unwrap();
+
+        assert!(profile.contains_marker("12:00:00 INFO PlayerKilled victim=Bob"));
+        assert!(!profile.contains_marker("12:00:00 INFO <Actor Death> not this game"));
+        assert!(!profile.contains_marker("12:00:00 INFO nothing here"));
+    }
+
+    #[test]
+    fn test_severity_regex_needs_group() {
+        assert!(LogProfile::compile(config(r"INFO|WARN")).is_err());
+        assert!(LogProfile::compile(config(r"(unclosed")).is_err());
+    }
+
+    #[test]
+    fn test_unknown_profile_id() {
+        assert_eq!(default_profile().id, DEFAULT_PROFILE_ID);
+        assert_eq!(
+            get_profile(Some("missing")).err(),
+            Some(LogError::UnknownProfile("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_timestamp_with_offset_format() {
+        let profile = LogProfile::compile(LogProfileConfig {
+            id: "offset_game".to_string(),
+            timestamp_regex: r"^\[([^\]]+)\]".to_string(),
+            timestamp_format: Some("%Y-%m-%d %H:%M:%S%.3f %z".to_string()),
+            utc_offset_minutes: None,
+            severity_regex: r"\b(INFO|WARN|ERROR)\b".to_string(),
+            markers: vec![],
+        })
+        .unwrap();
+        // 14:00 at +02:00 is 12:00 UTC
+        assert_eq!(
+            profile.parse_timestamp("[2024-01-01 14:00:01.250 +0200] INFO ready"),
+            Some(1_704_110_401_250)
+        );
+        assert_eq!(profile.parse_timestamp("no timestamp"), None);
+
+        // The built-in profile accepts RFC 3339 offsets as well as Z
+        assert_eq!(
+            default_profile().parse_timestamp("<2024-01-01T14:00:01.250+02:00> [Notice] ready"),
+            Some(1_704_110_401_250)
+        );
+    }
+
+    #[test]
+    fn test_timestamp_local_time_offset() {
+        let mut local = LogProfileConfig {
+            id: "local_game".to_string(),
+            timestamp_regex: r"^(\S+ \S+) ".to_string(),
+            timestamp_format: Some("%Y-%m-%d %H:%M:%S".to_string()),
+            utc_offset_minutes: Some(-300),
+            severity_regex: r"\b(INFO|WARN|ERROR)\b".to_string(),
+            markers: vec![],
+        };
+        let profile = LogProfile::compile(local.clone()).unwrap();
+        // 07:00 at UTC-5 is 12:00 UTC
+        assert_eq!(profile.parse_timestamp("2024-01-01 07:00:00 INFO ready"), Some(1_704_110_400_000));
+
+        local.utc_offset_minutes = Some(24 * 60);
+        assert!(LogProfile::compile(local.clone()).is_err());
+        local.utc_offset_minutes = None;
+        local.timestamp_format = Some("%Y-%Q".to_string());
+        assert!(LogProfile::compile(local).is_err());
+    }
+
+    #[test]
+    fn test_profile_config_round_trip() {
+        let mut original = config(r"\b(INFO|WARN|ERROR)\b");
+        original.timestamp_format = Some("%H:%M:%S".to_string());
+        original.utc_offset_minutes = Some(90);
+        let profile = LogProfile::compile(original).unwrap();
+
+        let exported = profile.config();
+        assert_eq!(exported.utc_offset_minutes, Some(90));
+        assert_eq!(exported.markers, vec!["PlayerKilled".to_string()]);
+        let recompiled = LogProfile::compile(exported).unwrap();
+        assert_eq!(recompiled.timestamp_re.as_str(), profile.timestamp_re.as_str());
+        assert_eq!(recompiled.severity_re.as_str(), profile.severity_re.as_str());
+    }
+
+    #[test]
+    fn test_profile_export_validation() {
+        let json = r#"{ "version": 1, "marker_rules": [{ "pattern": "(unclosed", "is_regex": true, "event_type": "x" }] }"#;
+        let export: ProfileExport = serde_json::from_str(json).unwrap();
+        assert!(export.validate().unwrap_err().contains("(unclosed"));
+
+        let mut export: ProfileExport = serde_json::from_str(&export_profile().unwrap()).unwrap();
+        assert!(export.validate().is_ok());
+        export.profiles.push(config(r"INFO"));
+        assert!(export.validate().unwrap_err().contains("other_game"));
+    }
+
+    #[test]
+    fn test_profile_export_rejects_empty_delimiter() {
+        let json = r#"{ "version": 1, "event_markers": ["<Custom>"], "player_name_config": { "delimiter": "" } }"#;
+        let export: ProfileExport = serde_json::from_str(json).unwrap();
+        assert!(export.validate().unwrap_err().contains("delimiter"));
+    }
+}
@@ -0,0 +1,122 @@
+//! Logs on network shares
+//!
+//! Some players keep SC on a NAS and point the app at a UNC path. Opens there are
+//! slow and fail transiently when the share drops for a moment, so they are retried
+//! with backoff, and a share that stays unreachable is reported as
+//! `LogError::Unavailable` rather than `NotFound`. Watchers poll such paths less often.
+
+use std::path::Path;
+use std::time::Duration;
+
+use crate::error::LogError;
+
+/// Waits between open attempts on a network path (after the first failure, and so on)
+const OPEN_RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_millis(50),
+    Duration::from_millis(200),
+    Duration::from_millis(800),
+];
+
+/// Debounce window for network paths when watch_log_file is given none
+pub const REMOTE_DEBOUNCE_MS: u64 = 1000;
+
+/// How often network paths are polled for changes
+pub const REMOTE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Whether a path is on a network share: a UNC path (`\\server\share\...`,
+/// `//server/share/...`) or its verbatim form (`\\?\UNC\server\share\...`).
+/// Mapped drive letters can't be told apart from local drives by the path alone.
+pub fn is_remote(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    if path.starts_with(r"\\?\") || path.starts_with(r"\\.\") {
+        return path.get(..8).is_some_and(|prefix| prefix.eq_ignore_ascii_case(r"\\?\UNC\"));
+    }
+    path.starts_with(r"\\") || path.starts_with("//")
+}
+
+/// Whether the folder holding `path` can be reached, i.e. the share itself is up
+fn share_reachable(path: &Path) -> bool {
+    path.parent().is_some_and(|dir| std::fs::metadata(dir).is_ok())
+}
+
+/// Run `open` on a network path, retrying after each of `delays` while it fails in a
+/// way a flaky share could explain. A file missing from a reachable share fails at
+/// once with NotFound; a share still unreachable after the last retry is Unavailable.
+pub fn retry_open<T>(
+    path: &Path,
+    delays: &[Duration],
+    mut open: impl FnMut(&Path) -> Result<T, LogError>,
+) -> Result<T, LogError> {
+    let mut delays = delays.iter();
+    loop {
+        let error = match open(path) {
+            Ok(opened) => return Ok(opened),
+            Err(e @ (LogError::NotAFile | LogError::PermissionDenied | LogError::UnknownProfile(_))) => return Err(e),
+            Err(LogError::NotFound) if share_reachable(path) => return Err(LogError::NotFound),
+            Err(e) => e,
+        };
+        match delays.next() {
+            Some(delay) => std::thread::sleep(*delay),
+            None => {
+                eprintln!("[Rust] Network log {} unavailable: {}", path.display(), error);
+                return Err(LogError::Unavailable);
+            }
+        }
+    }
+}
+
+/// retry_open with the default backoff
+pub fn open_with_retry<T>(path: &Path, open: impl FnMut(&Path) -> Result<T, LogError>) -> Result<T, LogError> {
+    retry_open(path, &OPEN_RETRY_DELAYS, open)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_remote() {
+        assert!(is_remote(Path::new(r"\\nas\games\StarCitizen\LIVE\Game.log")));
+        assert!(is_remote(Path::new("//nas/games/StarCitizen/LIVE/Game.log")));
+        assert!(is_remote(Path::new(r"\\?\UNC\nas\games\Game.log")));
+        assert!(!is_remote(Path::new(r"\\?\C:\Games\Game.log")));
+        assert!(!is_remote(Path::new(r"C:\Games\StarCitizen\LIVE\Game.log")));
+        assert!(!is_remote(Path::new("/home/pilot/Game.log")));
+    }
+
+    #[test]
+    fn test_retry_open_backs_off_then_reports_unavailable() {
+        let delays = [Duration::ZERO; 2];
+        let unreachable = Path::new("/no/such/share/Game.log");
+
+        // Recovers once the share answers again
+        let mut attempts = 0;
+        let opened = retry_open(unreachable, &delays, |_| {
+            attempts += 1;
+            if attempts < 3 {
+                Err(LogError::Io("network name no longer available".to_string()))
+            } else {
+                Ok(attempts)
+            }
+        });
+        assert_eq!(opened, Ok(3));
+
+        let mut attempts = 0;
+        let result: Result<(), _> = retry_open(unreachable, &delays, |_| {
+            attempts += 1;
+            Err(LogError::NotFound)
+        });
+        assert_eq!(result, Err(LogError::Unavailable));
+        assert_eq!(attempts, 3);
+
+        // The folder exists, so the file really is gone: no retries
+        let mut attempts = 0;
+        let missing = std::env::temp_dir().join("picologs_no_such_remote.log");
+        let result: Result<(), _> = retry_open(&missing, &delays, |_| {
+            attempts += 1;
+            Err(LogError::NotFound)
+        });
+        assert_eq!(result, Err(LogError::NotFound));
+        assert_eq!(attempts, 1);
+    }
+}
@@ -0,0 +1,133 @@
+//! Substring search over a log with surrounding context lines
+
+use std::collections::VecDeque;
+
+use crate::error::LogError;
+use crate::{decode_line, open_log_reader, read_raw_line};
+
+/// Hits returned by one search, so a common query can't flood the IPC channel
+const MAX_SEARCH_HITS: usize = 1000;
+
+/// A matching line and its neighbours
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct SearchHit {
+    /// Zero-based, as used for `from_line`
+    pub line_number: usize,
+    pub line: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// Find lines containing `query`, with up to `context_lines` lines before and after
+/// each. Case-insensitive unless `case_sensitive` is set; stops after MAX_SEARCH_HITS.
+#[tauri::command]
+pub fn search_log(
+    path: &str,
+    query: &str,
+    context_lines: usize,
+    case_sensitive: Option<bool>,
+) -> Result<Vec<SearchHit>, LogError> {
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let case_sensitive = case_sensitive.unwrap_or(false);
+    let needle = if case_sensitive { query.to_string() } else { query.to_lowercase() };
+
+    let mut reader = open_log_reader(path)?;
+    let mut buf = Vec::new();
+    let mut line_number = 0usize;
+
+    let mut recent: VecDeque<String> = VecDeque::with_capacity(context_lines + 1);
+    let mut hits: Vec<SearchHit> = Vec::new();
+    // Hits still collecting their trailing context (index into `hits`)
+    let mut open_hits: VecDeque<usize> = VecDeque::new();
+
+    while read_raw_line(&mut reader, &mut buf)? > 0 {
+        let line = decode_line(&buf).into_owned();
+
+        for &index in &open_hits {
+            hits[index].after.push(line.clone());
+        }
+        while open_hits.front().is_some_and(|&index| hits[index].after.len() == context_lines) {
+            open_hits.pop_front();
+        }
+
+        let matches = if case_sensitive {
+            line.contains(&needle)
+        } else {
+            line.to_lowercase().contains(&needle)
+        };
+        if matches && hits.len() < MAX_SEARCH_HITS {
+            hits.push(SearchHit {
+                line_number,
+                line: line.clone(),
+                before: recent.iter().cloned().collect(),
+                after: Vec::new(),
+            });
+            if context_lines > 0 {
+                open_hits.push_back(hits.len() - 1);
+            }
+        } else if hits.len() == MAX_SEARCH_HITS && open_hits.is_empty() {
+            break;
+        }
+
+        if context_lines > 0 {
+            if recent.len() == context_lines {
+                recent.pop_front();
+            }
+            recent.push_back(line);
+        }
+        line_number += 1;
+    }
+
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_log_context_and_case() {
+        let path = crate::tests::write_temp_log("search", "one\ntwo\nThree <Actor Death>\nfour\nfive\nsix <actor death>\n");
+        let path_str = path.to_str().unwrap();
+
+        let hits = search_log(path_str, "actor death", 1, None).unwrap();
+        assert_eq!(
+            hits,
+            vec![
+                SearchHit {
+                    line_number: 2,
+                    line: "Three <Actor Death>".to_string(),
+                    before: vec!["two".to_string()],
+                    after: vec!["four".to_string()],
+                },
+                SearchHit {
+                    line_number: 5,
+                    line: "six <actor death>".to_string(),
+                    before: vec!["five".to_string()],
+                    after: vec![],
+                },
+            ]
+        );
+
+        let hits = search_log(path_str, "Actor Death", 0, Some(true)).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].before.is_empty() && hits[0].after.is_empty());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_search_log_overlapping_context() {
+        let path = crate::tests::write_temp_log("search_overlap", "a\nhit 1\nhit 2\nb\nc\n");
+        let hits = search_log(path.to_str().unwrap(), "hit", 2, None).unwrap();
+
+        assert_eq!(hits[0].before, vec!["a"]);
+        assert_eq!(hits[0].after, vec!["hit 2", "b"]);
+        assert_eq!(hits[1].before, vec!["a", "hit 1"]);
+        assert_eq!(hits[1].after, vec!["b", "c"]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
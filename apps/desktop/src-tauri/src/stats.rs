@@ -0,0 +1,706 @@
+//! Session summary statistics computed in a single pass over the log
+//!
+//! Lets the summary panel update from one call instead of shipping every event over IPC.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::error::LogError;
+use crate::events::{classify_line, event_type_for_line, parse_timestamp};
+use crate::parsers;
+use crate::travel::JumpTracker;
+use crate::{
+    contains_event_marker, extract_player_name, for_each_line, player_name_config,
+    PlayerNameConfig, SEVERITY_RE, SEVERITY_TAGS, SUBSYSTEM_TAG_RE,
+};
+
+/// Totals for the lines from `from_line` onward
+#[derive(serde::Serialize, Debug, Default, PartialEq)]
+pub struct SessionStats {
+    /// Actor deaths caused by the logged-in player (suicides excluded)
+    pub kills: usize,
+    /// Actor deaths of the logged-in player
+    pub deaths: usize,
+    pub quantum_jumps: usize,
+    /// Distinct missions that ended as completed
+    pub missions_completed: usize,
+    pub items_purchased: usize,
+    /// Time between the first and last timestamped line
+    pub session_duration_ms: i64,
+}
+
+/// Accumulates SessionStats line by line
+struct StatsCollector {
+    from_line: usize,
+    /// Most recent login seen, including before from_line
+    player_name: Option<String>,
+    stats: SessionStats,
+    completed_missions: HashSet<String>,
+    jumps: JumpTracker,
+    deaths: parsers::DeathDeduper,
+    first_ts: Option<i64>,
+    last_ts: Option<i64>,
+    player_name_config: PlayerNameConfig,
+}
+
+/// Whether a mission event reports a successful completion
+fn is_mission_completed(event_type: &str, state: Option<&str>) -> bool {
+    match event_type {
+        "mission_ended" => state == Some("MISSION_STATE_COMPLETED"),
+        "end_mission" => state == Some("Complete"),
+        _ => false,
+    }
+}
+
+impl StatsCollector {
+    fn new(from_line: usize, dedup_window_ms: i64) -> Self {
+        Self {
+            from_line,
+            player_name: None,
+            stats: SessionStats::default(),
+            completed_missions: HashSet::new(),
+            jumps: JumpTracker::new(from_line),
+            deaths: parsers::DeathDeduper::new(dedup_window_ms),
+            first_ts: None,
+            last_ts: None,
+            player_name_config: player_name_config(),
+        }
+    }
+
+    fn push_line(&mut self, line_number: usize, line: &str) {
+        if let Some(name) = extract_player_name(line, &self.player_name_config) {
+            self.player_name = Some(name);
+        }
+        self.jumps.push_line(line_number, line);
+        if line_number < self.from_line {
+            return;
+        }
+
+        let timestamp_ms = parse_timestamp(line);
+        if let Some(ts) = timestamp_ms {
+            self.first_ts.get_or_insert(ts);
+            self.last_ts = Some(ts);
+        }
+
+        match event_type_for_line(line) {
+            Some("actor_death") => {
+                let Some(death) = parsers::parse_actor_death(line) else {
+                    return;
+                };
+                if self.deaths.is_duplicate(&death, timestamp_ms) {
+                    return;
+                }
+                let Some(player) = self.player_name.as_deref() else {
+                    return;
+                };
+                if parsers::is_same_player(&death.victim, player) {
+                    self.stats.deaths += 1;
+                } else if death.killer.as_deref().is_some_and(|killer| parsers::is_same_player(killer, player)) {
+                    self.stats.kills += 1;
+                }
+            }
+            Some("item_purchase") => self.stats.items_purchased += 1,
+            Some(event_type @ ("mission_ended" | "end_mission")) => {
+                if let Some(mission) = parsers::parse_mission_event(line) {
+                    if is_mission_completed(event_type, mission.state.as_deref()) {
+                        self.completed_missions.insert(mission.mission_id);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> SessionStats {
+        self.stats.quantum_jumps = self.jumps.finish().len();
+        self.stats.missions_completed = self.completed_missions.len();
+        if let (Some(first), Some(last)) = (self.first_ts, self.last_ts) {
+            self.stats.session_duration_ms = last - first;
+        }
+        self.stats
+    }
+}
+
+/// Kill, death, jump, mission and purchase totals from `from_line` onward.
+/// Kills and deaths are attributed to the character logged in at that point. A death
+/// logged again within `dedup_window_ms` (default DEFAULT_DEATH_DEDUP_WINDOW_MS, 0 to
+/// keep every line) counts once.
+#[tauri::command]
+pub fn compute_stats(path: &str, from_line: usize, dedup_window_ms: Option<i64>) -> Result<SessionStats, LogError> {
+    let window = dedup_window_ms.unwrap_or(parsers::DEFAULT_DEATH_DEDUP_WINDOW_MS);
+    let mut collector = StatsCollector::new(from_line, window);
+    // From the top: the login and jump origins may precede from_line
+    for_each_line(path, 0, |line_number, line| collector.push_line(line_number, line))?;
+    Ok(collector.finish())
+}
+
+/// A log aggregate_stats left out, and why
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct SkippedLog {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Totals over several logs, for the lifetime stats panel
+#[derive(serde::Serialize, Debug, Default, PartialEq)]
+pub struct AggregateStats {
+    pub kills: usize,
+    pub deaths: usize,
+    pub quantum_jumps: usize,
+    /// Kills per death; the kill count itself while there are no deaths
+    pub kd_ratio: f64,
+    /// Logs that were counted
+    pub logs: usize,
+    pub skipped: Vec<SkippedLog>,
+}
+
+impl AggregateStats {
+    fn add(&mut self, stats: &SessionStats) {
+        self.kills += stats.kills;
+        self.deaths += stats.deaths;
+        self.quantum_jumps += stats.quantum_jumps;
+        self.logs += 1;
+        self.kd_ratio = self.kills as f64 / self.deaths.max(1) as f64;
+    }
+}
+
+/// Kills, deaths and jumps summed over `paths` (one compute_stats pass each), with
+/// the overall K/D. Empty or unreadable logs are skipped and listed in `skipped`.
+#[tauri::command]
+pub fn aggregate_stats(paths: Vec<String>) -> AggregateStats {
+    let mut aggregate = AggregateStats::default();
+    for path in paths {
+        let stats = match std::fs::metadata(&path) {
+            Ok(metadata) if metadata.len() == 0 => Err("Log file is empty".to_string()),
+            _ => compute_stats(&path, 0, None).map_err(|e| e.to_string()),
+        };
+        match stats {
+            Ok(stats) => aggregate.add(&stats),
+            Err(reason) => aggregate.skipped.push(SkippedLog { path, reason }),
+        }
+    }
+    aggregate
+}
+
+/// Lines per severity tag, over all lines (not only marker-matched ones)
+#[derive(serde::Serialize, Debug, Default, PartialEq)]
+pub struct SeverityCounts {
+    pub notice: usize,
+    pub warning: usize,
+    pub error: usize,
+    pub trace: usize,
+    /// Lines without a recognized severity tag
+    pub other: usize,
+}
+
+impl SeverityCounts {
+    fn count_line(&mut self, line: &str) {
+        let bucket = match SEVERITY_RE.captures(line).as_ref().map(|caps| &caps[1]) {
+            Some("Notice") => &mut self.notice,
+            Some("Warning") => &mut self.warning,
+            Some("Error") => &mut self.error,
+            Some("Trace") => &mut self.trace,
+            _ => &mut self.other,
+        };
+        *bucket += 1;
+    }
+}
+
+/// Count lines from `from_line` onward by severity, for a session health badge
+#[tauri::command]
+pub fn count_severities(path: &str, from_line: usize) -> Result<SeverityCounts, LogError> {
+    let mut counts = SeverityCounts::default();
+    for_each_line(path, from_line, |_, line| counts.count_line(line))?;
+    Ok(counts)
+}
+
+/// Subsystems kept by subsystem_error_report, noisiest first
+const SUBSYSTEM_REPORT_LIMIT: usize = 20;
+
+/// Error and warning lines tagged with one subsystem
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct SubsystemErrors {
+    pub subsystem: String,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+/// Error and warning lines per subsystem tag, over all lines
+#[derive(Default)]
+struct SubsystemCounter {
+    counts: HashMap<String, (usize, usize)>,
+}
+
+impl SubsystemCounter {
+    fn count_line(&mut self, line: &str) {
+        let is_error = match SEVERITY_RE.captures(line).as_ref().map(|caps| &caps[1]) {
+            Some("Error") => true,
+            Some("Warning") => false,
+            _ => return,
+        };
+        let tags: HashSet<&str> = SUBSYSTEM_TAG_RE
+            .captures_iter(line)
+            .map(|caps| caps.get(1).unwrap().as_str())
+            .filter(|tag| !SEVERITY_TAGS.contains(tag) && !tag.starts_with("Team_"))
+            .collect();
+        for tag in tags {
+            let (errors, warnings) = self.counts.entry(tag.to_string()).or_default();
+            if is_error {
+                *errors += 1;
+            } else {
+                *warnings += 1;
+            }
+        }
+    }
+
+    /// The `limit` subsystems with the most errors, then warnings
+    fn finish(self, limit: usize) -> Vec<SubsystemErrors> {
+        let mut report: Vec<SubsystemErrors> = self
+            .counts
+            .into_iter()
+            .map(|(subsystem, (errors, warnings))| SubsystemErrors {
+                subsystem,
+                errors,
+                warnings,
+            })
+            .collect();
+        report.sort_by(|a, b| {
+            (b.errors, b.warnings)
+                .cmp(&(a.errors, a.warnings))
+                .then_with(|| a.subsystem.cmp(&b.subsystem))
+        });
+        report.truncate(limit);
+        report
+    }
+}
+
+/// Subsystems with the most error and warning lines from `from_line` onward, so a
+/// player can see which one was spamming errors before a crash
+#[tauri::command]
+pub fn subsystem_error_report(path: &str, from_line: usize) -> Result<Vec<SubsystemErrors>, LogError> {
+    let mut counter = SubsystemCounter::default();
+    for_each_line(path, from_line, |_, line| counter.count_line(line))?;
+    Ok(counter.finish(SUBSYSTEM_REPORT_LIMIT))
+}
+
+/// A stretch without any marker-matched event
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct IdleGap {
+    /// Timestamp of the last event before the gap, as Unix epoch milliseconds
+    pub start_ms: i64,
+    /// Timestamp of the first event after it
+    pub end_ms: i64,
+    pub duration_ms: i64,
+    /// Lines of those two events
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// Result of detect_idle_gaps
+#[derive(serde::Serialize, Debug, Default, PartialEq)]
+pub struct IdleGaps {
+    pub gaps: Vec<IdleGap>,
+    /// False if no event had a timestamp, in which case `gaps` is empty because the
+    /// gaps can't be measured rather than because there were none
+    pub has_timestamps: bool,
+}
+
+/// Gaps between consecutive timestamped events longer than `min_gap_ms`
+#[derive(Default)]
+struct GapFinder {
+    min_gap_ms: i64,
+    previous: Option<(i64, usize)>,
+    result: IdleGaps,
+}
+
+impl GapFinder {
+    fn push_line(&mut self, line_number: usize, line: &str) {
+        if !contains_event_marker(line) {
+            return;
+        }
+        let Some(ms) = parse_timestamp(line) else {
+            return;
+        };
+        self.result.has_timestamps = true;
+        if let Some((start_ms, start_line)) = self.previous {
+            if ms - start_ms > self.min_gap_ms {
+                self.result.gaps.push(IdleGap {
+                    start_ms,
+                    end_ms: ms,
+                    duration_ms: ms - start_ms,
+                    start_line,
+                    end_line: line_number,
+                });
+            }
+        }
+        self.previous = Some((ms, line_number));
+    }
+}
+
+/// Stretches of more than `min_gap_ms` between consecutive events, for splitting a
+/// long session into active play segments on the timeline
+#[tauri::command]
+pub fn detect_idle_gaps(path: &str, min_gap_ms: i64) -> Result<IdleGaps, LogError> {
+    let mut finder = GapFinder {
+        min_gap_ms,
+        ..GapFinder::default()
+    };
+    for_each_line(path, 0, |line_number, line| finder.push_line(line_number, line))?;
+    Ok(finder.result)
+}
+
+/// Marker matches in the trailing time window of a log
+struct RateCounter {
+    window_ms: i64,
+    /// Timestamps of marker matches no older than the window before `latest_ms`
+    recent: VecDeque<i64>,
+    /// Newest timestamp on any line, so quiet stretches still move the window forward
+    latest_ms: Option<i64>,
+    lines: usize,
+    events: usize,
+}
+
+impl RateCounter {
+    fn new(window_ms: i64) -> Self {
+        Self {
+            window_ms,
+            recent: VecDeque::new(),
+            latest_ms: None,
+            lines: 0,
+            events: 0,
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        self.lines += 1;
+        let timestamp = parse_timestamp(line);
+        if let Some(ms) = timestamp {
+            self.latest_ms = Some(self.latest_ms.map_or(ms, |latest| latest.max(ms)));
+        }
+        if contains_event_marker(line) {
+            self.events += 1;
+            if let Some(ms) = timestamp {
+                self.recent.push_back(ms);
+            }
+        }
+
+        if let Some(latest) = self.latest_ms {
+            while self.recent.front().is_some_and(|&ms| ms < latest - self.window_ms) {
+                self.recent.pop_front();
+            }
+        }
+    }
+
+    /// Events per minute over the window. Without timestamps the window can't be
+    /// placed, so the rate falls back to marker matches per 60 lines read.
+    fn rate(&self) -> f64 {
+        if self.latest_ms.is_none() {
+            if self.lines == 0 {
+                return 0.0;
+            }
+            return self.events as f64 * 60.0 / self.lines as f64;
+        }
+        if self.window_ms <= 0 {
+            return 0.0;
+        }
+        self.recent.len() as f64 * 60_000.0 / self.window_ms as f64
+    }
+}
+
+/// Events per minute in the last `window_ms` of the log (measured from its newest
+/// timestamp), reading from `from_line`. Drives the live activity indicator.
+#[tauri::command]
+pub fn event_rate(path: &str, from_line: usize, window_ms: i64) -> Result<f64, LogError> {
+    let mut counter = RateCounter::new(window_ms);
+    for_each_line(path, from_line, |_, line| counter.push_line(line))?;
+    Ok(counter.rate())
+}
+
+/// An event type present in a log, for building filter checkboxes
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct EventTypeInfo {
+    pub event_type: String,
+    pub count: usize,
+    pub first_seen_ms: Option<i64>,
+    pub last_seen_ms: Option<i64>,
+}
+
+/// Event types in order of first appearance
+#[derive(Default)]
+struct EventTypeCounter {
+    types: Vec<EventTypeInfo>,
+    index_by_type: HashMap<String, usize>,
+}
+
+impl EventTypeCounter {
+    fn push_line(&mut self, line: &str) {
+        if !contains_event_marker(line) {
+            return;
+        }
+        let event_type = classify_line(line);
+        let index = match self.index_by_type.get(&event_type) {
+            Some(&index) => index,
+            None => {
+                self.index_by_type.insert(event_type.clone(), self.types.len());
+                self.types.push(EventTypeInfo {
+                    event_type,
+                    count: 0,
+                    first_seen_ms: None,
+                    last_seen_ms: None,
+                });
+                self.types.len() - 1
+            }
+        };
+        let info = &mut self.types[index];
+        info.count += 1;
+        if let Some(ts) = parse_timestamp(line) {
+            info.first_seen_ms.get_or_insert(ts);
+            info.last_seen_ms = Some(ts);
+        }
+    }
+}
+
+/// Every event type (built-in or from a marker rule) that occurs from `from_line`
+/// onward, with its count and first and last timestamp, in order of first appearance
+#[tauri::command]
+pub fn list_event_types(path: &str, from_line: usize) -> Result<Vec<EventTypeInfo>, LogError> {
+    let mut counter = EventTypeCounter::default();
+    for_each_line(path, from_line, |_, line| counter.push_line(line))?;
+    Ok(counter.types)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "\
+<2024-01-01T12:00:00.000Z> AccountLoginCharacterStatus_Character - name Pilot - state 1
+<2024-01-01T12:01:00.000Z> <Actor Death> CActor::Kill: 'Enemy' [2] in zone 'z' killed by 'Pilot' [1] using 'gun' with damage type 'Bullet'
+<2024-01-01T12:02:00.000Z> <Actor Death> CActor::Kill: 'Pilot' [1] in zone 'z' killed by 'Enemy' [2] using 'gun' with damage type 'Bullet'
+<2024-01-01T12:03:00.000Z> <Actor Death> CActor::Kill: 'Npc' [3] in zone 'z' killed by 'Enemy' [2] using 'gun' with damage type 'Bullet'
+<2024-01-01T12:04:00.000Z> <Jump Drive Requesting State Change> Requested change from Idle to Spooling, reason: spool | Stanton | (player: AEGS_Gladius_9 in zone Crusader)
+<2024-01-01T12:05:00.000Z> <Quantum Drive Arrived - Arrived at Final Destination> AEGS_Gladius_9[9]|CSCItemNavigation::OnQuantumDriveArrived|arrived
+<2024-01-01T12:06:00.000Z> <MissionEnded> Received MissionEnded push message for: mission_id abc-1 - mission_state MISSION_STATE_COMPLETED
+<2024-01-01T12:06:01.000Z> <EndMission> Ending mission for player. MissionId[abc-1] Player[Pilot] PlayerId[1] CompletionType[Complete] Reason[Done]
+<2024-01-01T12:07:00.000Z> <EndMission> Ending mission for player. MissionId[abc-2] Player[Pilot] PlayerId[1] CompletionType[Abort] Reason[PlayerAborted]
+<2024-01-01T12:10:00.000Z> <CEntityComponentShoppingProvider::SendStandardItemBuyRequest> buy
+";
+
+    fn collect(from_line: usize) -> SessionStats {
+        let mut collector = StatsCollector::new(from_line, parsers::DEFAULT_DEATH_DEDUP_WINDOW_MS);
+        for (i, line) in LOG.lines().enumerate() {
+            collector.push_line(i, line);
+        }
+        collector.finish()
+    }
+
+    #[test]
+    fn test_stats_totals() {
+        assert_eq!(
+            collect(0),
+            SessionStats {
+                kills: 1,
+                deaths: 1,
+                quantum_jumps: 1,
+                missions_completed: 1,
+                items_purchased: 1,
+                session_duration_ms: 10 * 60 * 1000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats_match_player_case_insensitively() {
+        let mut collector = StatsCollector::new(0, parsers::DEFAULT_DEATH_DEDUP_WINDOW_MS);
+        let log = LOG.replacen("name Pilot", "name pilot", 1);
+        for (i, line) in log.lines().enumerate() {
+            collector.push_line(i, line);
+        }
+        let stats = collector.finish();
+        assert_eq!((stats.kills, stats.deaths), (1, 1));
+    }
+
+    #[test]
+    fn test_stats_from_line_keeps_player_from_earlier_login() {
+        let stats = collect(2);
+        assert_eq!(stats.kills, 0);
+        assert_eq!(stats.deaths, 1);
+        assert_eq!(stats.session_duration_ms, 8 * 60 * 1000);
+    }
+
+    #[test]
+    fn test_stats_count_double_logged_death_once() {
+        let death = |ts: &str| {
+            format!(
+                "<2024-01-01T12:01:{}Z> <Actor Death> CActor::Kill: 'Enemy' [2] in zone 'z' killed by 'Pilot' [1] using 'gun' with damage type 'Bullet'",
+                ts
+            )
+        };
+        let lines = [
+            "<2024-01-01T12:00:00.000Z> AccountLoginCharacterStatus_Character - name Pilot - state 1".to_string(),
+            death("00.000"),
+            death("00.400"),
+            // Respawned and killed again later
+            death("30.000"),
+        ];
+        let count_kills = |window_ms: i64| {
+            let mut collector = StatsCollector::new(0, window_ms);
+            for (i, line) in lines.iter().enumerate() {
+                collector.push_line(i, line);
+            }
+            collector.finish().kills
+        };
+
+        assert_eq!(count_kills(parsers::DEFAULT_DEATH_DEDUP_WINDOW_MS), 2);
+        assert_eq!(count_kills(0), 3);
+    }
+
+    #[test]
+    fn test_count_severities() {
+        let mut counts = SeverityCounts::default();
+        for line in LOG.lines().chain([
+            "<2024-01-01T12:00:00.000Z> [Notice] ok",
+            "<2024-01-01T12:00:00.000Z> [Error] broken",
+            "<2024-01-01T12:00:00.000Z> [Warning] odd",
+            "<2024-01-01T12:00:00.000Z> [Trace] detail",
+        ]) {
+            counts.count_line(line);
+        }
+
+        assert_eq!(
+            counts,
+            SeverityCounts {
+                notice: 1,
+                warning: 1,
+                error: 1,
+                trace: 1,
+                other: LOG.lines().count(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_subsystem_error_report_ranks_noisiest() {
+        let mut counter = SubsystemCounter::default();
+        for line in [
+            "<2024-01-01T12:00:00.000Z> [Error] <Net> dropped [Network][Team_Net]",
+            "<2024-01-01T12:00:01.000Z> [Error] <Net> dropped again [Network][Network]",
+            "<2024-01-01T12:00:02.000Z> [Warning] <Load> slow [Streaming]",
+            "<2024-01-01T12:00:03.000Z> [Error] <Load> failed [Streaming]",
+            "<2024-01-01T12:00:04.000Z> [Warning] <Ui> odd [Ui]",
+            "<2024-01-01T12:00:05.000Z> [Notice] <Ui> fine [Ui][Audio]",
+        ] {
+            counter.count_line(line);
+        }
+
+        let report = counter.finish(2);
+        assert_eq!(
+            report,
+            vec![
+                SubsystemErrors {
+                    subsystem: "Network".to_string(),
+                    errors: 2,
+                    warnings: 0,
+                },
+                SubsystemErrors {
+                    subsystem: "Streaming".to_string(),
+                    errors: 1,
+                    warnings: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_idle_gaps() {
+        let mut finder = GapFinder {
+            min_gap_ms: 60_000,
+            ..GapFinder::default()
+        };
+        for (i, line) in LOG.lines().enumerate() {
+            finder.push_line(i, line);
+        }
+        // Only the purchase after three quiet minutes is far enough from its predecessor
+        assert_eq!(
+            finder.result.gaps,
+            vec![IdleGap {
+                start_ms: 1_704_110_820_000,
+                end_ms: 1_704_111_000_000,
+                duration_ms: 3 * 60 * 1000,
+                start_line: 8,
+                end_line: 9,
+            }]
+        );
+        assert!(finder.result.has_timestamps);
+
+        let mut untimed = GapFinder::default();
+        untimed.push_line(0, "<Actor Death> no timestamp");
+        assert_eq!(untimed.result, IdleGaps::default());
+    }
+
+    #[test]
+    fn test_event_rate_window() {
+        let mut counter = RateCounter::new(60_000);
+        for line in [
+            "<2024-01-01T12:00:00.000Z> [Notice] <Actor Death> too old",
+            "<2024-01-01T12:01:30.000Z> [Notice] <Actor Death> in window",
+            "<2024-01-01T12:01:45.000Z> [Notice] <SystemQuit> in window",
+            "<2024-01-01T12:02:00.000Z> [Trace] idle in a menu",
+        ] {
+            counter.push_line(line);
+        }
+        assert_eq!(counter.rate(), 2.0);
+
+        // No timestamps: matches per 60 lines
+        let mut counter = RateCounter::new(60_000);
+        for line in ["<Actor Death> a", "plain", "plain", "plain"] {
+            counter.push_line(line);
+        }
+        assert_eq!(counter.rate(), 15.0);
+    }
+
+    #[test]
+    fn test_event_type_counter() {
+        let mut counter = EventTypeCounter::default();
+        for line in LOG.lines() {
+            counter.push_line(line);
+        }
+        let deaths = &counter.types[1];
+
+        assert_eq!(counter.types[0].event_type, "player_login");
+        assert_eq!(deaths.event_type, "actor_death");
+        assert_eq!(deaths.count, 3);
+        assert_eq!(deaths.first_seen_ms, parse_timestamp("<2024-01-01T12:01:00.000Z>"));
+        assert_eq!(deaths.last_seen_ms, parse_timestamp("<2024-01-01T12:03:00.000Z>"));
+        assert_eq!(counter.types.iter().filter(|info| info.event_type == "end_mission").count(), 1);
+    }
+
+    #[test]
+    fn test_aggregate_stats_skips_missing_and_empty_logs() {
+        let full = crate::tests::write_temp_log("aggregate_full", LOG);
+        let empty = crate::tests::write_temp_log("aggregate_empty", "");
+        let missing = std::env::temp_dir().join("picologs_test_aggregate_missing.log");
+        let paths = [&full, &full, &empty, &missing].map(|path| path.to_string_lossy().into_owned());
+
+        let aggregate = aggregate_stats(paths.to_vec());
+        assert_eq!((aggregate.kills, aggregate.deaths, aggregate.quantum_jumps), (2, 2, 2));
+        assert_eq!(aggregate.kd_ratio, 1.0);
+        assert_eq!(aggregate.logs, 2);
+        assert_eq!(
+            aggregate.skipped,
+            vec![
+                SkippedLog {
+                    path: paths[2].clone(),
+                    reason: "Log file is empty".to_string(),
+                },
+                SkippedLog {
+                    path: paths[3].clone(),
+                    reason: LogError::NotFound.to_string(),
+                },
+            ]
+        );
+
+        for path in [full, empty] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
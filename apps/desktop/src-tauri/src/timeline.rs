@@ -0,0 +1,128 @@
+//! Merging several logs into one chronological event timeline
+//!
+//! Each log is already in time order, so the files are streamed and k-way merged:
+//! only one pending event per file is held besides the output.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::io::BufRead;
+
+use crate::error::LogError;
+use crate::events::{parse_event, ParsedEvent};
+use crate::{decode_line, open_log_reader, read_raw_line};
+
+/// Parsed events of one log, read lazily
+struct EventStream {
+    reader: Box<dyn BufRead + Send>,
+    buf: Vec<u8>,
+    /// Sort key of the previous event, used for events without a timestamp
+    last_ms: i64,
+}
+
+impl EventStream {
+    fn open(path: &str) -> Result<Self, LogError> {
+        Ok(Self {
+            reader: open_log_reader(path)?,
+            buf: Vec::new(),
+            last_ms: i64::MIN,
+        })
+    }
+
+    /// Next event and its sort key. An event without a timestamp sorts with the one before it.
+    fn next_event(&mut self) -> Result<Option<(i64, ParsedEvent)>, LogError> {
+        while read_raw_line(&mut self.reader, &mut self.buf)? > 0 {
+            if let Some(event) = parse_event(&decode_line(&self.buf)) {
+                self.last_ms = event.timestamp_ms.unwrap_or(self.last_ms);
+                return Ok(Some((self.last_ms, event)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Parsed events of all `paths` in one timeline ordered by `timestamp_ms`.
+/// Ties keep the order of `paths`; a line that appears in several files (overlapping
+/// copies of a session) is returned once.
+#[tauri::command]
+pub fn merge_logs(paths: Vec<String>) -> Result<Vec<ParsedEvent>, LogError> {
+    let mut streams = paths
+        .iter()
+        .map(|path| EventStream::open(path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut pending: Vec<Option<ParsedEvent>> = Vec::with_capacity(streams.len());
+    let mut heap = BinaryHeap::new();
+    for (index, stream) in streams.iter_mut().enumerate() {
+        let next = stream.next_event()?;
+        if let Some((ms, _)) = &next {
+            heap.push(Reverse((*ms, index)));
+        }
+        pending.push(next.map(|(_, event)| event));
+    }
+
+    let mut merged = Vec::new();
+    // Raw lines already emitted at the current timestamp (duplicates share a timestamp)
+    let mut current_ms = None;
+    let mut seen_at_ms: HashSet<String> = HashSet::new();
+
+    while let Some(Reverse((ms, index))) = heap.pop() {
+        let event = pending[index].take().expect("heap entry without pending event");
+        if current_ms != Some(ms) {
+            current_ms = Some(ms);
+            seen_at_ms.clear();
+        }
+        if seen_at_ms.insert(event.raw.clone()) {
+            merged.push(event);
+        }
+
+        if let Some((next_ms, next)) = streams[index].next_event()? {
+            heap.push(Reverse((next_ms, index)));
+            pending[index] = Some(next);
+        }
+    }
+
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn death(time: &str, victim: &str) -> String {
+        format!(
+            "<2024-01-01T{}Z> [Notice] <Actor Death> CActor::Kill: '{}' [1] in zone 'z' killed by 'K' [2] using 'w' with damage type 'Combat'\n",
+            time, victim
+        )
+    }
+
+    #[test]
+    fn test_merge_logs_orders_and_dedupes() {
+        let first = crate::tests::write_temp_log(
+            "merge_first",
+            &[death("12:00:00.000", "A"), "plain line\n".to_string(), death("12:00:02.000", "C")].concat(),
+        );
+        // Overlaps the first log: C is in both
+        let second = crate::tests::write_temp_log(
+            "merge_second",
+            &[death("12:00:01.000", "B"), death("12:00:02.000", "C"), death("12:00:03.000", "D")].concat(),
+        );
+
+        let merged = merge_logs(vec![
+            first.to_str().unwrap().to_string(),
+            second.to_str().unwrap().to_string(),
+        ])
+        .unwrap();
+        let victims: Vec<&str> = merged.iter().map(|e| e.attributes["victim"].as_str()).collect();
+
+        assert_eq!(victims, vec!["A", "B", "C", "D"]);
+
+        std::fs::remove_file(first).unwrap();
+        std::fs::remove_file(second).unwrap();
+    }
+
+    #[test]
+    fn test_merge_logs_missing_file() {
+        let result = merge_logs(vec!["/no/such/picologs_merge.log".to_string()]);
+        assert_eq!(result.err(), Some(LogError::NotFound));
+    }
+}
@@ -0,0 +1,186 @@
+//! Quantum travel history reconstructed from jump drive and arrival lines
+//!
+//! `<Quantum Drive Arrived` only says that a jump finished. The zones come from the
+//! `(player: SHIP in zone ZONE)` suffix of `<Jump Drive Requesting State Change>` lines:
+//! the zone reported when the drive leaves Idle is the origin, the first zone reported
+//! after the arrival is the destination.
+//!
+//! The same state change lines also give the spool timeline: every drive transition
+//! with its time, for analyzing how long spooling and cooldown take.
+
+use crate::error::LogError;
+use crate::events::parse_timestamp;
+use crate::parsers::{self, VehicleAction};
+use crate::for_each_line;
+
+/// One completed quantum jump
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct QuantumJump {
+    /// None when the jump started before any zone was logged (first jump after spawn)
+    pub from: Option<String>,
+    /// None when no zone was logged after the arrival (log ended mid-session)
+    pub to: Option<String>,
+    /// Arrival time as Unix epoch milliseconds
+    pub timestamp_ms: Option<i64>,
+}
+
+/// Follows the player's drive state line by line and collects finished jumps
+#[derive(Default)]
+pub(crate) struct JumpTracker {
+    from_line: usize,
+    current_zone: Option<String>,
+    /// Origin of the jump in progress, set once the drive leaves Idle
+    departure: Option<Option<String>>,
+    /// Arrived jump waiting for its destination zone
+    pending: Option<QuantumJump>,
+    pending_visible: bool,
+    /// Entity id of the player's current vehicle, to ignore other ships arriving nearby
+    player_vehicle: Option<String>,
+    jumps: Vec<QuantumJump>,
+}
+
+impl JumpTracker {
+    pub(crate) fn new(from_line: usize) -> Self {
+        Self {
+            from_line,
+            ..Self::default()
+        }
+    }
+
+    fn flush_pending(&mut self, to: Option<String>) {
+        if let Some(mut jump) = self.pending.take() {
+            if self.pending_visible {
+                jump.to = to;
+                self.jumps.push(jump);
+            }
+        }
+    }
+
+    pub(crate) fn push_line(&mut self, line_number: usize, line: &str) {
+        if let Some(vehicle) = parsers::parse_vehicle_control(line) {
+            self.player_vehicle = match vehicle.action {
+                VehicleAction::Enter => Some(vehicle.vehicle_geid),
+                VehicleAction::Exit => None,
+            };
+        } else if let Some(state) = parsers::parse_jump_drive_state(line) {
+            if let Some(geid) = state.vehicle.as_deref().and_then(|v| v.rsplit('_').next()) {
+                self.player_vehicle = Some(geid.to_string());
+            }
+            if state.zone.is_some() {
+                self.flush_pending(state.zone.clone());
+                self.current_zone = state.zone.clone();
+            }
+            if state.from_state == "Idle" && state.to_state != "Idle" {
+                self.departure = Some(self.current_zone.clone());
+            }
+        } else if let Some(arrival) = parsers::parse_quantum_arrival(line) {
+            let other_ship = self
+                .player_vehicle
+                .as_ref()
+                .is_some_and(|geid| *geid != arrival.vehicle_geid);
+            if other_ship {
+                return;
+            }
+
+            self.flush_pending(None);
+            self.pending = Some(QuantumJump {
+                from: self.departure.take().flatten(),
+                to: None,
+                timestamp_ms: parse_timestamp(line),
+            });
+            self.pending_visible = line_number >= self.from_line;
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> Vec<QuantumJump> {
+        self.flush_pending(None);
+        self.jumps
+    }
+}
+
+/// Quantum jumps that arrived at or after `from_line`, in log order.
+/// Earlier lines are still read so a jump's origin is known.
+#[tauri::command]
+pub fn read_quantum_jumps(path: &str, from_line: usize) -> Result<Vec<QuantumJump>, LogError> {
+    let mut tracker = JumpTracker::new(from_line);
+    for_each_line(path, 0, |line_number, line| tracker.push_line(line_number, line))?;
+    Ok(tracker.finish())
+}
+
+/// Jump drive state transitions from `from_line` onward, in log order
+#[tauri::command]
+pub fn read_spool_timeline(path: &str, from_line: usize) -> Result<Vec<parsers::JumpDriveState>, LogError> {
+    let mut timeline = Vec::new();
+    for_each_line(path, from_line, |_, line| timeline.extend(parsers::parse_jump_drive_state(line)))?;
+    Ok(timeline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drive_line(time: &str, from: &str, to: &str, zone: &str) -> String {
+        format!(
+            "<2025-11-02T{}Z> [Notice] <Jump Drive Requesting State Change> Requested change from {} to {}, reason: state | Stanton | (player: AEGS_Gladius_123 in zone {})",
+            time, from, to, zone
+        )
+    }
+
+    fn arrival_line(time: &str, vehicle: &str) -> String {
+        format!(
+            "<2025-11-02T{}Z> [Notice] <Quantum Drive Arrived - Arrived at Final Destination> {}|CSCItemNavigation::OnQuantumDriveArrived|Quantum Drive has arrived at final destination",
+            time, vehicle
+        )
+    }
+
+    fn track(lines: &[String], from_line: usize) -> Vec<QuantumJump> {
+        let mut tracker = JumpTracker::new(from_line);
+        for (i, line) in lines.iter().enumerate() {
+            tracker.push_line(i, line);
+        }
+        tracker.finish()
+    }
+
+    #[test]
+    fn test_jump_origin_and_destination() {
+        let lines = vec![
+            drive_line("07:00:00.000", "Idle", "Spooling", "Crusader"),
+            arrival_line("07:01:00.000", "AEGS_Gladius_123[123]"),
+            drive_line("07:01:01.000", "Traveling", "Idle", "Hurston"),
+        ];
+        let jumps = track(&lines, 0);
+
+        assert_eq!(jumps.len(), 1);
+        assert_eq!(jumps[0].from.as_deref(), Some("Crusader"));
+        assert_eq!(jumps[0].to.as_deref(), Some("Hurston"));
+        assert_eq!(jumps[0].timestamp_ms, parse_timestamp(&lines[1]));
+    }
+
+    #[test]
+    fn test_first_jump_without_origin() {
+        let lines = vec![arrival_line("07:01:00.000", "AEGS_Gladius_123[123]")];
+        let jumps = track(&lines, 0);
+
+        assert_eq!(jumps, vec![QuantumJump { from: None, to: None, timestamp_ms: parse_timestamp(&lines[0]) }]);
+    }
+
+    #[test]
+    fn test_other_ship_arrivals_ignored_and_from_line_respected() {
+        let lines = vec![
+            drive_line("07:00:00.000", "Idle", "Spooling", "Crusader"),
+            arrival_line("07:00:30.000", "ANVL_Hornet_999[999]"),
+            arrival_line("07:01:00.000", "AEGS_Gladius_123[123]"),
+            drive_line("07:01:01.000", "Traveling", "Idle", "Hurston"),
+            drive_line("07:05:00.000", "Idle", "Spooling", "Hurston"),
+            arrival_line("07:06:00.000", "AEGS_Gladius_123[123]"),
+            drive_line("07:06:01.000", "Traveling", "Idle", "ArcCorp"),
+        ];
+
+        assert_eq!(track(&lines, 0).len(), 2);
+
+        let jumps = track(&lines, 4);
+        assert_eq!(jumps.len(), 1);
+        assert_eq!(jumps[0].from.as_deref(), Some("Hurston"));
+        assert_eq!(jumps[0].to.as_deref(), Some("ArcCorp"));
+    }
+}
@@ -0,0 +1,196 @@
+//! Uploading parsed events to a remote endpoint
+//!
+//! For org dashboards and stats sites. New events from a line cursor are read and
+//! POSTed as JSON one batch at a time; each batch that the endpoint accepts advances
+//! the cursor, so an upload cut short by a network failure can resume without sending
+//! events twice.
+
+use std::io::BufRead;
+use std::time::Duration;
+
+use tauri_plugin_http::reqwest;
+
+use crate::error::LogError;
+use crate::events::{parse_event, ParsedEvent};
+use crate::{decode_line, open_log_reader, read_raw_line};
+
+/// Number of events per POST
+const UPLOAD_BATCH_SIZE: usize = 500;
+
+/// How long one POST may take before it counts as a network failure
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Events to POST together and the cursor to resume from once they are accepted
+#[derive(Debug)]
+struct UploadBatch {
+    events: Vec<ParsedEvent>,
+    next_line: usize,
+}
+
+/// Outcome of upload_events
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct UploadProgress {
+    /// Line to pass as `from_line` next time: past every uploaded event
+    pub next_line: usize,
+    /// Events the endpoint accepted
+    pub uploaded: usize,
+    /// Why the upload stopped early; `next_line` is where it stopped
+    pub error: Option<LogError>,
+}
+
+/// Reads the events from `from_line` onward in batches of `batch_size`. The last
+/// batch resumes at the end of the file, so lines with no events after the last event
+/// aren't read again. A final line SC is still writing is left for the next upload.
+struct BatchReader {
+    reader: Box<dyn BufRead + Send>,
+    buf: Vec<u8>,
+    line_count: usize,
+    from_line: usize,
+    batch_size: usize,
+    done: bool,
+}
+
+impl BatchReader {
+    fn open(path: &str, from_line: usize, batch_size: usize) -> Result<Self, LogError> {
+        Ok(Self {
+            reader: open_log_reader(path)?,
+            buf: Vec::new(),
+            line_count: 0,
+            from_line,
+            batch_size,
+            done: false,
+        })
+    }
+
+    /// Next batch, or None once the batch ending at the end of the file was returned
+    fn next_batch(&mut self) -> Result<Option<UploadBatch>, LogError> {
+        if self.done {
+            return Ok(None);
+        }
+        let mut events = Vec::with_capacity(self.batch_size);
+        loop {
+            let bytes_read = read_raw_line(&mut self.reader, &mut self.buf)?;
+            // No newline was stripped: end of file or a partial line
+            if bytes_read == self.buf.len() {
+                self.done = true;
+                break;
+            }
+            self.line_count += 1;
+            if self.line_count <= self.from_line {
+                continue;
+            }
+            if let Some(event) = parse_event(&decode_line(&self.buf)) {
+                events.push(event);
+                if events.len() == self.batch_size {
+                    break;
+                }
+            }
+        }
+        Ok(Some(UploadBatch {
+            events,
+            next_line: self.line_count,
+        }))
+    }
+}
+
+/// Run blocking file I/O off the async runtime
+async fn run_blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> Result<T, LogError> {
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| LogError::Io(format!("Failed to read log: {}", e)))
+}
+
+/// Map a failed or rejected POST to the error reported to the frontend
+fn upload_error(result: Result<reqwest::Response, reqwest::Error>) -> Option<LogError> {
+    match result {
+        Ok(response) if response.status().is_success() => None,
+        Ok(response) => Some(LogError::Rejected(response.status().as_u16())),
+        Err(e) => Some(LogError::Network(e.to_string())),
+    }
+}
+
+/// POST the events after `from_line` to `endpoint` as `{ "events": [...] }` in
+/// batches, with `auth` as a bearer token. Returns the cursor to resume from: the end
+/// of the file on success, or the line after the last accepted batch if a POST fails.
+#[tauri::command]
+pub async fn upload_events(
+    path: String,
+    from_line: usize,
+    endpoint: String,
+    auth: Option<String>,
+) -> Result<UploadProgress, LogError> {
+    let client = reqwest::Client::builder()
+        .timeout(UPLOAD_TIMEOUT)
+        .build()
+        .map_err(|e| LogError::Network(e.to_string()))?;
+
+    let mut progress = UploadProgress {
+        next_line: from_line,
+        uploaded: 0,
+        error: None,
+    };
+    let mut reader = run_blocking(move || BatchReader::open(&path, from_line, UPLOAD_BATCH_SIZE)).await??;
+    loop {
+        let (returned, batch) = run_blocking(move || {
+            let batch = reader.next_batch();
+            (reader, batch)
+        })
+        .await?;
+        reader = returned;
+        let Some(batch) = batch? else {
+            break;
+        };
+        if !batch.events.is_empty() {
+            let body = serde_json::to_vec(&serde_json::json!({ "events": batch.events }))
+                .map_err(|e| LogError::Io(format!("Failed to serialize events: {}", e)))?;
+            let mut request = client
+                .post(&endpoint)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body);
+            if let Some(token) = &auth {
+                request = request.bearer_auth(token);
+            }
+            if let Some(error) = upload_error(request.send().await) {
+                progress.error = Some(error);
+                break;
+            }
+            progress.uploaded += batch.events.len();
+        }
+        progress.next_line = batch.next_line;
+    }
+    Ok(progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_batches() {
+        let path = crate::tests::write_temp_log(
+            "upload_batches",
+            "<2024-01-01T12:00:00.000Z> <SystemQuit> skipped\n\
+             <2024-01-01T12:00:01.000Z> <SystemQuit> one\n\
+             <2024-01-01T12:00:02.000Z> plain line\n\
+             <2024-01-01T12:00:03.000Z> <SystemQuit> two\n\
+             <2024-01-01T12:00:04.000Z> <SystemQuit> three\n\
+             <2024-01-01T12:00:05.000Z> trailing plain line\n",
+        );
+
+        let read_batches = |from_line| {
+            let mut reader = BatchReader::open(path.to_str().unwrap(), from_line, 2).unwrap();
+            std::iter::from_fn(|| reader.next_batch().unwrap()).collect::<Vec<_>>()
+        };
+
+        let batches = read_batches(1);
+        let shape: Vec<(usize, usize)> = batches.iter().map(|b| (b.events.len(), b.next_line)).collect();
+        assert_eq!(shape, vec![(2, 4), (1, 6)]);
+        assert!(batches[0].events[0].raw.ends_with("one"));
+
+        let batches = read_batches(6);
+        let shape: Vec<(usize, usize)> = batches.iter().map(|b| (b.events.len(), b.next_line)).collect();
+        assert_eq!(shape, vec![(0, 6)]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
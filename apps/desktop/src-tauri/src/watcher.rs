@@ -0,0 +1,922 @@
+//! Native filesystem watching for log files
+//!
+//! Replaces JavaScript timer polling: the frontend calls `watch_log_file` once and only
+//! re-reads the log when a `log-file-changed` event arrives. Appended lines are
+//! also checked for the character login, reported as `player-identified`.
+//! Changes arriving in a burst (SC flushes heavily during combat) are coalesced
+//! into one event per debounce window. read_log_blocking offers the same wake-ups
+//! as a long-poll for callers that would rather not listen for events.
+//! Logs on network shares are polled, with a longer default debounce.
+//! A periodic `watch-heartbeat` tells an idle but healthy watch from a dead one.
+//! A symlinked log is watched at its target (where the writes happen); events still
+//! carry the path the frontend passed in.
+//! Appended `[Error]` lines are counted too: a burst of them usually comes right
+//! before SC crashes, and is reported as `log-error-spike`.
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use notify::{Event, EventHandler, RecursiveMode, Watcher};
+use tauri::{AppHandle, Emitter};
+
+use crate::error::LogError;
+use crate::events::parse_timestamp;
+use crate::remote;
+use crate::{
+    decode_line, extract_player_name, open_log_file, player_name_config, resolve_log_path, LogUpdate, ReadCancellation,
+    SEVERITY_RE,
+};
+
+/// Payload of the `log-file-changed` event
+#[derive(serde::Serialize, Clone)]
+pub struct LogFileChanged {
+    path: String,
+    size: u64,
+}
+
+/// Payload of the `watch-heartbeat` event
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct WatchHeartbeat {
+    path: String,
+    /// When the file last changed size, as Unix epoch milliseconds (its modification
+    /// time until a change is seen)
+    last_change_ms: Option<i64>,
+    /// The file grew since the previous heartbeat
+    is_growing: bool,
+}
+
+/// Payload of the `player-identified` event
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct PlayerIdentified {
+    path: String,
+    player_name: String,
+    /// Line of the login event
+    line: usize,
+}
+
+/// Payload of the `log-error-spike` event
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct ErrorSpike {
+    path: String,
+    /// `[Error]` lines within the window
+    error_count: usize,
+    window_ms: i64,
+    /// The most recent error lines, oldest first
+    sample: Vec<String>,
+}
+
+/// When watch_log_file reports an error spike
+#[derive(serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ErrorSpikeConfig {
+    /// `[Error]` lines within `window_ms` (by log timestamp) that make a spike
+    pub threshold: usize,
+    pub window_ms: i64,
+}
+
+impl Default for ErrorSpikeConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 20,
+            window_ms: 10_000,
+        }
+    }
+}
+
+/// Error lines kept as the sample of an ErrorSpike
+const ERROR_SPIKE_SAMPLE_LINES: usize = 5;
+
+/// Sliding window over the timestamps of appended error lines. A spike is reported
+/// at most once per window, so a flood emits one event every `window_ms`.
+struct ErrorSpikeDetector {
+    config: ErrorSpikeConfig,
+    recent: VecDeque<i64>,
+    sample: VecDeque<String>,
+    last_spike_ms: Option<i64>,
+}
+
+impl ErrorSpikeDetector {
+    fn new(config: ErrorSpikeConfig) -> Self {
+        Self {
+            config,
+            recent: VecDeque::new(),
+            sample: VecDeque::new(),
+            last_spike_ms: None,
+        }
+    }
+
+    /// Count `line` if it is a timestamped error, returning the spike it completes
+    fn push_line(&mut self, path: &str, line: &str) -> Option<ErrorSpike> {
+        if SEVERITY_RE.captures(line).as_ref().map(|caps| &caps[1]) != Some("Error") {
+            return None;
+        }
+        let ms = parse_timestamp(line)?;
+
+        self.recent.push_back(ms);
+        while self.recent.front().is_some_and(|&oldest| oldest < ms - self.config.window_ms) {
+            self.recent.pop_front();
+        }
+        if self.sample.len() == ERROR_SPIKE_SAMPLE_LINES {
+            self.sample.pop_front();
+        }
+        self.sample.push_back(line.to_string());
+
+        let cooled_down = self.last_spike_ms.is_none_or(|last| ms - last >= self.config.window_ms);
+        if self.recent.len() < self.config.threshold.max(1) || !cooled_down {
+            return None;
+        }
+        self.last_spike_ms = Some(ms);
+        Some(ErrorSpike {
+            path: path.to_string(),
+            error_count: self.recent.len(),
+            window_ms: self.config.window_ms,
+            sample: self.sample.iter().cloned().collect(),
+        })
+    }
+}
+
+/// How far the scan of appended lines has read, and the name it last reported
+#[derive(Default)]
+struct PlayerScan {
+    /// Start of the first line not yet scanned
+    offset: u64,
+    line_count: usize,
+    player_name: Option<String>,
+    /// Set after the first scan: lines already in the file when the watch started are
+    /// not checked for error spikes (an old crash is not news)
+    caught_up: bool,
+}
+
+/// What a scan of appended lines found
+#[derive(Debug, Default, PartialEq)]
+struct AppendedFindings {
+    identified: Vec<PlayerIdentified>,
+    spikes: Vec<ErrorSpike>,
+}
+
+/// Debounce window used when watch_log_file is given none
+const DEFAULT_DEBOUNCE_MS: u64 = 250;
+
+/// What to do with a change notification
+#[derive(Debug, PartialEq)]
+enum DebounceAction {
+    EmitNow,
+    /// Emit once this much time has passed (the window is still open)
+    EmitAfter(Duration),
+    /// An emit is already scheduled and will pick this change up
+    Coalesce,
+}
+
+/// Leading- and trailing-edge debounce: the first change in a quiet period is
+/// emitted at once, later ones are folded into a single emit when the window ends
+#[derive(Default)]
+struct Debounce {
+    last_emit: Option<Instant>,
+    pending: bool,
+}
+
+impl Debounce {
+    fn on_change(&mut self, now: Instant, window: Duration) -> DebounceAction {
+        if self.pending {
+            return DebounceAction::Coalesce;
+        }
+        match self.last_emit {
+            Some(last) if now.duration_since(last) < window => {
+                self.pending = true;
+                DebounceAction::EmitAfter(window - now.duration_since(last))
+            }
+            _ => {
+                self.last_emit = Some(now);
+                DebounceAction::EmitNow
+            }
+        }
+    }
+
+    /// Record that the scheduled emit went out
+    fn on_scheduled_emit(&mut self, now: Instant) {
+        self.pending = false;
+        self.last_emit = Some(now);
+    }
+}
+
+/// Time between `watch-heartbeat` events
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// File sizes seen by successive heartbeats
+struct Liveness {
+    last_size: u64,
+    last_change_ms: Option<i64>,
+}
+
+impl Liveness {
+    /// Record the size seen at `now_ms`, returning whether the file grew since the last tick
+    fn tick(&mut self, size: u64, now_ms: i64) -> bool {
+        let grew = size > self.last_size;
+        if size != self.last_size {
+            self.last_change_ms = Some(now_ms);
+        }
+        self.last_size = size;
+        grew
+    }
+}
+
+pub(crate) fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since_epoch| since_epoch.as_millis() as i64)
+}
+
+/// Emit `watch-heartbeat` every HEARTBEAT_INTERVAL until the watch is dropped
+fn spawn_heartbeat(state: Arc<WatchState>, app: AppHandle, metadata: &std::fs::Metadata) {
+    let mut liveness = Liveness {
+        last_size: metadata.len(),
+        last_change_ms: crate::modified_ms(metadata),
+    };
+    std::thread::spawn(move || loop {
+        std::thread::sleep(HEARTBEAT_INTERVAL);
+        if state.stopped.load(Ordering::SeqCst) {
+            break;
+        }
+        // A file briefly missing while the game recreates it is reported as not growing
+        let is_growing = std::fs::metadata(&state.file).is_ok_and(|metadata| liveness.tick(metadata.len(), now_ms()));
+        let payload = WatchHeartbeat {
+            path: state.path.clone(),
+            last_change_ms: liveness.last_change_ms,
+            is_growing,
+        };
+        if let Err(e) = app.emit("watch-heartbeat", payload) {
+            eprintln!("[Rust] Failed to emit watch-heartbeat event: {}", e);
+        }
+    });
+}
+
+/// Gap between the two size samples of is_live_log
+const LIVE_SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// A log modified this recently is being written to
+const LIVE_RECENT_MS: i64 = 5_000;
+
+/// A log ending mid-line counts as mid-write if modified within this long; older ones
+/// were cut off by a crash
+const LIVE_MID_WRITE_MS: i64 = 60_000;
+
+/// Live-log verdict from the samples: growth between them, time since the last write
+/// and whether the file ends in a partial line
+fn looks_live(grew: bool, modified_ago_ms: Option<i64>, ends_mid_line: bool) -> bool {
+    let modified_within = |ms: i64| modified_ago_ms.is_some_and(|ago| ago <= ms);
+    grew || modified_within(LIVE_RECENT_MS) || (ends_mid_line && modified_within(LIVE_MID_WRITE_MS))
+}
+
+/// Whether a log is being written right now (the game's current Game.log) rather than
+/// a static archive, for a LIVE badge. Takes two size samples 200ms apart.
+#[tauri::command(async)]
+pub fn is_live_log(path: String) -> Result<bool, LogError> {
+    let mut file = open_log_file(&path)?;
+    let head = crate::read_log_head(&mut file)?;
+    if crate::is_gzip(&path, &head) {
+        return Ok(false);
+    }
+
+    let first_size = file.metadata()?.len();
+    std::thread::sleep(LIVE_SAMPLE_INTERVAL);
+    let metadata = file.metadata()?;
+
+    let ends_mid_line = match metadata.len().checked_sub(1) {
+        Some(last) => {
+            let mut byte = [0u8; 1];
+            file.seek(SeekFrom::Start(last))?;
+            file.read_exact(&mut byte)?;
+            byte[0] != b'\n'
+        }
+        None => false,
+    };
+    let modified_ago_ms = crate::modified_ms(&metadata).map(|modified| now_ms() - modified);
+
+    Ok(looks_live(metadata.len() != first_size, modified_ago_ms, ends_mid_line))
+}
+
+/// Shared between a watcher's callback and the registry
+struct WatchState {
+    /// Path as supplied by the frontend (echoed back in events)
+    path: String,
+    file: PathBuf,
+    last_size: AtomicU64,
+    player_scan: Mutex<PlayerScan>,
+    error_spikes: Mutex<ErrorSpikeDetector>,
+    debounce_window: Duration,
+    debounce: Mutex<Debounce>,
+    /// Set once the watch is dropped, so a scheduled emit doesn't fire after unwatch
+    stopped: AtomicBool,
+}
+
+/// A live watcher for one log file. Dropping it stops the underlying OS watch.
+struct ActiveWatch {
+    _watcher: Box<dyn Watcher + Send>,
+    state: Arc<WatchState>,
+}
+
+impl Drop for ActiveWatch {
+    fn drop(&mut self) {
+        self.state.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+// Active watchers keyed by the path passed to watch_log_file
+lazy_static! {
+    static ref WATCHERS: Mutex<HashMap<String, ActiveWatch>> = Mutex::new(HashMap::new());
+}
+
+/// Directory to watch for a log file.
+/// The parent is watched instead of the file itself so the watch survives SC
+/// replacing Game.log on restart.
+fn watch_dir(file: &Path) -> PathBuf {
+    match file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+/// Native watcher for local logs. Change notifications from network shares are
+/// unreliable, so those are polled instead.
+fn new_watcher(file: &Path, handler: impl EventHandler) -> notify::Result<Box<dyn Watcher + Send>> {
+    if remote::is_remote(file) {
+        let config = notify::Config::default().with_poll_interval(remote::REMOTE_POLL_INTERVAL);
+        return Ok(Box::new(notify::PollWatcher::new(handler, config)?));
+    }
+    Ok(Box::new(notify::recommended_watcher(handler)?))
+}
+
+/// Scan the complete lines appended since the last scan for character logins, with
+/// one entry per change of name (a character switch mid-file re-reports), and for
+/// error spikes. A shrunken file is rescanned from the start.
+fn scan_appended(state: &WatchState, size: u64) -> Result<AppendedFindings, LogError> {
+    let mut scan = state.player_scan.lock().unwrap();
+    if size < scan.offset {
+        *scan = PlayerScan {
+            caught_up: true,
+            ..PlayerScan::default()
+        };
+    }
+    let mut errors = state.error_spikes.lock().unwrap();
+
+    let mut file = open_log_file(&state.file)?;
+    file.seek(SeekFrom::Start(scan.offset))?;
+    let mut reader = BufReader::new(file);
+
+    let config = player_name_config();
+    let mut findings = AppendedFindings::default();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        let bytes_read = reader.read_until(b'\n', &mut buf)?;
+        // Leave a line SC is still writing for the next scan
+        if bytes_read == 0 || buf.last() != Some(&b'\n') {
+            break;
+        }
+
+        let line = decode_line(&buf);
+        let line = line.trim_end();
+        if scan.caught_up {
+            findings.spikes.extend(errors.push_line(&state.path, line));
+        }
+        if let Some(name) = extract_player_name(line, &config) {
+            if scan.player_name.as_ref() != Some(&name) {
+                findings.identified.push(PlayerIdentified {
+                    path: state.path.clone(),
+                    player_name: name.clone(),
+                    line: scan.line_count,
+                });
+                scan.player_name = Some(name);
+            }
+        }
+        scan.offset += bytes_read as u64;
+        scan.line_count += 1;
+    }
+    scan.caught_up = true;
+
+    Ok(findings)
+}
+
+/// Emit `player-identified` for each login and `log-error-spike` for each error spike
+/// found since the last scan
+fn emit_appended_findings(state: &WatchState, app: &AppHandle, size: u64) {
+    match scan_appended(state, size) {
+        Ok(findings) => {
+            for payload in findings.identified {
+                if let Err(e) = app.emit("player-identified", payload) {
+                    eprintln!("[Rust] Failed to emit player-identified event: {}", e);
+                }
+            }
+            for payload in findings.spikes {
+                if let Err(e) = app.emit("log-error-spike", payload) {
+                    eprintln!("[Rust] Failed to emit log-error-spike event: {}", e);
+                }
+            }
+        }
+        Err(e) => eprintln!("[Rust] Failed to scan log for player login: {}", e),
+    }
+}
+
+/// Emit `log-file-changed` with the file's current size, then report any new login
+/// or error spike
+fn emit_change(state: &WatchState, app: &AppHandle) {
+    let size = state.last_size.load(Ordering::SeqCst);
+    let payload = LogFileChanged {
+        path: state.path.clone(),
+        size,
+    };
+    if let Err(e) = app.emit("log-file-changed", payload) {
+        eprintln!("[Rust] Failed to emit log-file-changed event: {}", e);
+    }
+    emit_appended_findings(state, app, size);
+}
+
+/// Handle a raw notify event: emit `log-file-changed` when the file size moved,
+/// at most once per debounce window
+fn on_fs_event(state: &Arc<WatchState>, app: &AppHandle, event: Event) {
+    let affects_file = event
+        .paths
+        .iter()
+        .any(|p| p.file_name() == state.file.file_name());
+    if !affects_file {
+        return;
+    }
+
+    // File may be briefly missing while the game recreates it
+    let Ok(metadata) = std::fs::metadata(&state.file) else {
+        return;
+    };
+
+    // Ignore metadata-only changes (access time etc.). A shrink is reported too:
+    // the follow-up read detects the rotation.
+    let size = metadata.len();
+    if state.last_size.swap(size, Ordering::SeqCst) == size {
+        return;
+    }
+
+    let action = state
+        .debounce
+        .lock()
+        .unwrap()
+        .on_change(Instant::now(), state.debounce_window);
+    match action {
+        DebounceAction::EmitNow => emit_change(state, app),
+        DebounceAction::EmitAfter(delay) => {
+            let state = Arc::clone(state);
+            let app = app.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(delay);
+                state.debounce.lock().unwrap().on_scheduled_emit(Instant::now());
+                if !state.stopped.load(Ordering::SeqCst) {
+                    emit_change(&state, &app);
+                }
+            });
+        }
+        DebounceAction::Coalesce => {}
+    }
+}
+
+/// Start watching a log file, emitting `log-file-changed` whenever it grows
+/// (coalesced over `debounce_ms`, default 250ms or 1s on a network share; 0 emits
+/// every change).
+/// A login already in the file is reported as `player-identified` right away, and
+/// `watch-heartbeat` is emitted every 5s while the watch is active. Appended error
+/// lines past `error_spike` (default 20 within 10s) emit `log-error-spike`.
+/// Watching a path that is already watched is a no-op.
+#[tauri::command]
+pub fn watch_log_file(
+    path: String,
+    debounce_ms: Option<u64>,
+    error_spike: Option<ErrorSpikeConfig>,
+    app: AppHandle,
+) -> Result<(), LogError> {
+    let mut watchers = WATCHERS.lock().unwrap();
+    if watchers.contains_key(&path) {
+        return Ok(());
+    }
+
+    let file = resolve_log_path(&path);
+    let metadata = std::fs::metadata(&file)?;
+    if metadata.is_dir() {
+        return Err(LogError::NotAFile);
+    }
+    let initial_size = metadata.len();
+    let default_debounce_ms = if remote::is_remote(&file) {
+        remote::REMOTE_DEBOUNCE_MS
+    } else {
+        DEFAULT_DEBOUNCE_MS
+    };
+
+    let state = Arc::new(WatchState {
+        path: path.clone(),
+        file: file.clone(),
+        last_size: AtomicU64::new(initial_size),
+        player_scan: Mutex::new(PlayerScan::default()),
+        error_spikes: Mutex::new(ErrorSpikeDetector::new(error_spike.unwrap_or_default())),
+        debounce_window: Duration::from_millis(debounce_ms.unwrap_or(default_debounce_ms)),
+        debounce: Mutex::new(Debounce::default()),
+        stopped: AtomicBool::new(false),
+    });
+    emit_appended_findings(&state, &app, initial_size);
+
+    let callback_state = Arc::clone(&state);
+    let heartbeat_app = app.clone();
+    let mut watcher = new_watcher(&file, move |res: notify::Result<Event>| match res {
+        Ok(event) => on_fs_event(&callback_state, &app, event),
+        Err(e) => eprintln!("[Rust] File watcher error: {}", e),
+    })?;
+
+    watcher.watch(&watch_dir(&file), RecursiveMode::NonRecursive)?;
+    spawn_heartbeat(Arc::clone(&state), heartbeat_app, &metadata);
+
+    watchers.insert(
+        path,
+        ActiveWatch {
+            _watcher: watcher,
+            state,
+        },
+    );
+    Ok(())
+}
+
+/// Stop watching a log file. Returns false if the path was not being watched.
+#[tauri::command]
+pub fn unwatch_log_file(path: String) -> bool {
+    WATCHERS.lock().unwrap().remove(&path).is_some()
+}
+
+/// Whether a log is being watched, under any spelling of its path
+pub fn is_watched(path: &Path) -> bool {
+    let target = crate::resolve_log_path(path);
+    WATCHERS
+        .lock()
+        .unwrap()
+        .keys()
+        .any(|watched| crate::resolve_log_path(watched) == target)
+}
+
+/// Drop every active watcher (called on app exit)
+pub fn unwatch_all() {
+    WATCHERS.lock().unwrap().clear();
+}
+
+/// Longest a blocked read sleeps between checks of the file and its cancellation token
+const BLOCKING_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Starts the watcher that wakes a blocked read, sending on `tx` for every event in
+/// the log's directory
+type WatchFn = fn(&Path, mpsc::Sender<()>) -> notify::Result<Box<dyn Watcher + Send>>;
+
+fn watch_log_dir(file: &Path, tx: mpsc::Sender<()>) -> notify::Result<Box<dyn Watcher + Send>> {
+    let mut watcher = new_watcher(file, move |_: notify::Result<Event>| {
+        let _ = tx.send(());
+    })?;
+    watcher.watch(&watch_dir(file), RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+/// Wait up to `timeout` for a watcher event and drain any others queued. Once the
+/// watcher is gone (it failed to start and dropped its sender) sleep instead, so the
+/// caller still polls every `timeout` rather than spinning.
+fn wait_for_event(rx: &mpsc::Receiver<()>, timeout: Duration) {
+    if let Err(RecvTimeoutError::Disconnected) = rx.recv_timeout(timeout) {
+        std::thread::sleep(timeout);
+    }
+    while rx.try_recv().is_ok() {}
+}
+
+/// Long-poll variant of read_log_update: waits until complete lines are appended after
+/// `from_line` (or the log rotates), up to `timeout_ms`, then returns the delta.
+/// On timeout the update is empty and `line_count` is still `from_line`.
+///
+/// Wake-ups come from a filesystem watcher, with the file re-checked every 100ms (1s
+/// on a network share) in case a notification is missed. Pass a unique `token` to abort the wait via
+/// cancel_read; the update then comes back empty and flagged `cancelled`.
+#[tauri::command(async)]
+pub fn read_log_blocking(
+    path: String,
+    from_line: usize,
+    timeout_ms: u64,
+    extract_player_name: bool,
+    extract_patterns: bool,
+    token: Option<String>,
+) -> Result<LogUpdate, LogError> {
+    block_for_update(
+        path,
+        from_line,
+        timeout_ms,
+        extract_player_name,
+        extract_patterns,
+        token,
+        watch_log_dir,
+    )
+}
+
+/// read_log_blocking with the watcher started by `watch`
+fn block_for_update(
+    path: String,
+    from_line: usize,
+    timeout_ms: u64,
+    extract_player_name: bool,
+    extract_patterns: bool,
+    token: Option<String>,
+    watch: WatchFn,
+) -> Result<LogUpdate, LogError> {
+    let cancellation = ReadCancellation::new(token);
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+    let file = resolve_log_path(&path);
+    let poll_interval = if remote::is_remote(&file) {
+        remote::REMOTE_POLL_INTERVAL
+    } else {
+        BLOCKING_POLL_INTERVAL
+    };
+    let scan = || crate::scan_log_update(&path, from_line, extract_player_name, extract_patterns, None, None, None, None);
+    let has_news = |update: &LogUpdate| update.rotated || update.committed_line_count > from_line;
+
+    // Watch before the first scan so lines written in between still wake us
+    let (tx, rx) = mpsc::channel();
+    let watcher = watch(&file, tx);
+    if let Err(e) = &watcher {
+        eprintln!("[Rust] Blocking read falling back to polling: {}", e);
+    }
+
+    let mut size = std::fs::metadata(&file)?.len();
+    let mut update = scan()?;
+    loop {
+        if has_news(&update) {
+            return Ok(update);
+        }
+        if cancellation.is_cancelled() {
+            update.cancelled = true;
+            return Ok(update);
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(update);
+        }
+
+        // Any event in the directory (or the poll interval) triggers a size check
+        wait_for_event(&rx, remaining.min(poll_interval));
+
+        // The game may briefly remove the file while recreating it
+        let Ok(metadata) = std::fs::metadata(&file) else {
+            continue;
+        };
+        if metadata.len() != size {
+            size = metadata.len();
+            update = scan()?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_dir_uses_parent() {
+        let file = Path::new("/games/StarCitizen/LIVE/Game.log");
+        assert_eq!(watch_dir(file), PathBuf::from("/games/StarCitizen/LIVE"));
+    }
+
+    #[test]
+    fn test_watch_dir_bare_file_name() {
+        assert_eq!(watch_dir(Path::new("Game.log")), PathBuf::from("."));
+    }
+
+    #[test]
+    fn test_debounce_coalesces_bursts() {
+        let window = Duration::from_millis(250);
+        let start = Instant::now();
+        let mut debounce = Debounce::default();
+
+        assert_eq!(debounce.on_change(start, window), DebounceAction::EmitNow);
+        assert_eq!(
+            debounce.on_change(start + Duration::from_millis(100), window),
+            DebounceAction::EmitAfter(Duration::from_millis(150))
+        );
+        assert_eq!(debounce.on_change(start + Duration::from_millis(200), window), DebounceAction::Coalesce);
+
+        debounce.on_scheduled_emit(start + Duration::from_millis(250));
+        assert_eq!(
+            debounce.on_change(start + Duration::from_millis(300), window),
+            DebounceAction::EmitAfter(Duration::from_millis(200))
+        );
+        debounce.on_scheduled_emit(start + Duration::from_millis(500));
+        assert_eq!(debounce.on_change(start + Duration::from_secs(1), window), DebounceAction::EmitNow);
+    }
+
+    #[test]
+    fn test_zero_window_emits_every_change() {
+        let now = Instant::now();
+        let mut debounce = Debounce::default();
+        assert_eq!(debounce.on_change(now, Duration::ZERO), DebounceAction::EmitNow);
+        assert_eq!(debounce.on_change(now, Duration::ZERO), DebounceAction::EmitNow);
+    }
+
+    #[test]
+    fn test_scan_appended_reports_name_changes() {
+        let login = |name: &str| {
+            format!("<2024-01-01T12:00:00.000Z> [Notice] <AccountLoginCharacterStatus_Character> Character: geid 1 - name {} - state STATE_CURRENT\n", name)
+        };
+        let first = format!("plain line\n{}", login("Pilot-One"));
+        let path = crate::tests::write_temp_log("player_identified", &first);
+        let state = WatchState {
+            path: "Game.log".to_string(),
+            file: path.clone(),
+            last_size: AtomicU64::new(0),
+            player_scan: Mutex::new(PlayerScan::default()),
+            error_spikes: Mutex::new(ErrorSpikeDetector::new(ErrorSpikeConfig::default())),
+            debounce_window: Duration::from_millis(DEFAULT_DEBOUNCE_MS),
+            debounce: Mutex::new(Debounce::default()),
+            stopped: AtomicBool::new(false),
+        };
+        let size = || std::fs::metadata(&path).unwrap().len();
+
+        let identified = scan_appended(&state, size()).unwrap().identified;
+        assert_eq!(
+            identified,
+            vec![PlayerIdentified {
+                path: "Game.log".to_string(),
+                player_name: "Pilot-One".to_string(),
+                line: 1,
+            }]
+        );
+
+        // Same character logging in again is not re-reported; a switch is
+        let grown = format!("{}{}{}", first, login("Pilot-One"), login("Pilot Two"));
+        std::fs::write(&path, &grown).unwrap();
+        let identified = scan_appended(&state, size()).unwrap().identified;
+        assert_eq!(identified.len(), 1);
+        assert_eq!(identified[0].player_name, "Pilot Two");
+        assert_eq!(identified[0].line, 3);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_looks_live() {
+        assert!(looks_live(true, Some(3_600_000), false));
+        assert!(looks_live(false, Some(1_000), false));
+        assert!(looks_live(false, Some(30_000), true));
+        // Cut off by a crash long ago
+        assert!(!looks_live(false, Some(3_600_000), true));
+        assert!(!looks_live(false, Some(30_000), false));
+        assert!(!looks_live(false, None, false));
+
+        let path = crate::tests::write_temp_log("live_log", "<Actor Death> mid-wri");
+        assert!(is_live_log(path.to_str().unwrap().to_string()).unwrap());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_error_spike_detector() {
+        let config = ErrorSpikeConfig {
+            threshold: 3,
+            window_ms: 1000,
+        };
+        let mut detector = ErrorSpikeDetector::new(config);
+        let error = |ms: u32| format!("<2024-01-01T12:00:0{}.{:03}Z> [Error] <Net> dropped", ms / 1000, ms % 1000);
+
+        // Spread out: never three within a second
+        for ms in [0, 600, 1200, 1800] {
+            assert_eq!(detector.push_line("Game.log", &error(ms)), None);
+        }
+        assert_eq!(detector.push_line("Game.log", "<2024-01-01T12:00:01.900Z> [Notice] fine"), None);
+
+        let spike = detector.push_line("Game.log", &error(1900)).unwrap();
+        assert_eq!(spike.error_count, 3);
+        assert_eq!(spike.sample.len(), 5);
+        assert_eq!(spike.sample.last(), Some(&error(1900)));
+
+        // Still flooding, but reported once per window
+        assert_eq!(detector.push_line("Game.log", &error(2000)), None);
+        assert!(detector.push_line("Game.log", &error(2900)).is_some());
+    }
+
+    #[test]
+    fn test_read_log_blocking_times_out_with_unchanged_cursor() {
+        let path = crate::tests::write_temp_log("blocking_timeout", "<Actor Death> first\n");
+        let path_str = path.to_str().unwrap().to_string();
+
+        let update = read_log_blocking(path_str, 1, 50, false, false, None).unwrap();
+        assert_eq!(update.line_count, 1);
+        assert!(update.new_lines.is_empty());
+        assert!(!update.cancelled);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_log_blocking_wakes_on_append() {
+        let path = crate::tests::write_temp_log("blocking_append", "<Actor Death> first\n");
+        let path_str = path.to_str().unwrap().to_string();
+
+        let writer_path = path.clone();
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(150));
+            // Appended rather than rewritten: a read between truncate and write would see a rotation
+            let mut file = std::fs::OpenOptions::new().append(true).open(writer_path).unwrap();
+            std::io::Write::write_all(&mut file, b"<Actor Death> second\n").unwrap();
+        });
+
+        let update = read_log_blocking(path_str, 1, 10_000, false, false, None).unwrap();
+        writer.join().unwrap();
+        assert_eq!(update.line_count, 2);
+        assert_eq!(update.new_lines, vec!["<Actor Death> second".to_string()]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_log_blocking_cancelled() {
+        let path = crate::tests::write_temp_log("blocking_cancel", "<Actor Death> first\n");
+        let path_str = path.to_str().unwrap().to_string();
+        let token = "blocking-cancel-test".to_string();
+
+        crate::cancel_read(token.clone());
+        let started = Instant::now();
+        let update = read_log_blocking(path_str, 1, 10_000, false, false, Some(token)).unwrap();
+        assert!(update.cancelled);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    fn failing_watch(_: &Path, _: mpsc::Sender<()>) -> notify::Result<Box<dyn Watcher + Send>> {
+        Err(notify::Error::generic("watcher unavailable"))
+    }
+
+    #[test]
+    fn test_wait_for_event_sleeps_without_watcher() {
+        let (tx, rx) = mpsc::channel::<()>();
+        drop(tx);
+
+        let started = Instant::now();
+        wait_for_event(&rx, Duration::from_millis(50));
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_read_log_blocking_polls_when_watcher_fails() {
+        let path = crate::tests::write_temp_log("blocking_polling", "<Actor Death> first\n");
+        let path_str = path.to_str().unwrap().to_string();
+
+        let writer_path = path.clone();
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(150));
+            let mut file = std::fs::OpenOptions::new().append(true).open(writer_path).unwrap();
+            std::io::Write::write_all(&mut file, b"<Actor Death> second\n").unwrap();
+        });
+
+        let update = block_for_update(path_str, 1, 10_000, false, false, None, failing_watch).unwrap();
+        writer.join().unwrap();
+        assert_eq!(update.line_count, 2);
+        assert_eq!(update.new_lines, vec!["<Actor Death> second".to_string()]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_liveness_tracks_growth() {
+        let mut liveness = Liveness {
+            last_size: 100,
+            last_change_ms: Some(1_000),
+        };
+
+        assert!(liveness.tick(150, 5_000));
+        assert_eq!(liveness.last_change_ms, Some(5_000));
+
+        // Idle: not growing, last change kept
+        assert!(!liveness.tick(150, 10_000));
+        assert_eq!(liveness.last_change_ms, Some(5_000));
+
+        // Truncated by a restart: a change, but not growth
+        assert!(!liveness.tick(10, 15_000));
+        assert_eq!(liveness.last_change_ms, Some(15_000));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_log_resolves_to_target() {
+        let target = crate::tests::write_temp_log("symlink_target", "<Actor Death> first\n");
+        let link = std::env::temp_dir().join(format!("picologs_symlink_{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        assert_eq!(resolve_log_path(&link), std::fs::canonicalize(&target).unwrap());
+        assert_eq!(watch_dir(&resolve_log_path(&link)), std::fs::canonicalize(target.parent().unwrap()).unwrap());
+
+        // Reads through the link see the target's contents
+        let update = read_log_blocking(link.to_str().unwrap().to_string(), 0, 0, false, false, None).unwrap();
+        assert_eq!(update.new_lines, vec!["<Actor Death> first".to_string()]);
+
+        std::fs::remove_file(link).unwrap();
+        std::fs::remove_file(target).unwrap();
+    }
+}
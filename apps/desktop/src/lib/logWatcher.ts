@@ -64,13 +64,19 @@ interface RawLogPattern {
 	subsystems: string[];
 	signature: string;
 	exampleLine: string;
+	// Timestamp of the line it was first seen on (epoch ms)
+	firstSeenMs: number | null;
 }
 
 // Type for Rust LogUpdate response (single-pass reading)
 interface LogUpdate {
 	line_count: number;
+	// Excludes a trailing line SC is still writing - resume from here
+	committed_line_count: number;
 	player_name: string | null;
 	new_lines: string[];
+	// Line number of each new_lines entry
+	line_numbers: number[];
 	patterns: RawLogPattern[];
 }
 
@@ -199,7 +205,7 @@ export async function startLogWatcher(
 			throw new Error(`Failed to read log file: ${errorMessage}`);
 		}
 
-		const currentLineCount = update.line_count;
+		const currentLineCount = update.committed_line_count;
 		let playerName = update.player_name;
 
 		// Handle truncation detection
@@ -214,6 +220,7 @@ export async function startLogWatcher(
 			});
 			playerName = truncUpdate.player_name;
 			update.new_lines = truncUpdate.new_lines;
+			update.line_numbers = truncUpdate.line_numbers;
 			update.patterns = truncUpdate.patterns;
 		} else if (!needsPlayerNameScan) {
 			// Use cached player name
@@ -314,7 +321,7 @@ export async function startLogWatcher(
 				return; // Skip this update, watcher will retry on next change
 			}
 
-			const currentLineCount = update.line_count;
+			const currentLineCount = update.committed_line_count;
 			const newLines = update.new_lines;
 
 			// Detect truncation: file has fewer lines than we've already read